@@ -0,0 +1,108 @@
+//! Generates the tests of `advent_of_code::example_tests!`, one per part of every example listed in
+//! the manifests `data/examples/NN.toml`. The manifests are validated when the tests run.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+// NOTE: the same parser reads `aoc.toml` and the manifests at runtime, so both agree on their content.
+#[allow(dead_code, unused_imports)]
+#[path = "src/template/toml.rs"]
+mod toml;
+
+use toml::{parse_toml, Value};
+
+fn main() {
+    let examples = examples_folder();
+    println!("cargo:rerun-if-changed=aoc.toml");
+    println!("cargo:rerun-if-changed=src/template/toml.rs");
+    println!("cargo:rerun-if-changed={}", examples.display());
+
+    let out =
+        PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("example_tests");
+    fs::create_dir_all(&out).expect("could not create folder for example tests");
+
+    for day in 1..=25 {
+        let manifest =
+            fs::read_to_string(examples.join(format!("{day:02}.toml"))).unwrap_or_default();
+        fs::write(out.join(format!("{day}.rs")), tests(&manifest))
+            .expect("could not write example tests");
+    }
+}
+
+/// The examples folder. Only `examples` and `data` of `[paths]` in `aoc.toml` affect it.
+/// An invalid `aoc.toml` is reported by the runner, the default folder is used here.
+fn examples_folder() -> PathBuf {
+    let config =
+        parse_toml(&fs::read_to_string("aoc.toml").unwrap_or_default()).unwrap_or_default();
+
+    match (
+        string(&config, "paths.examples"),
+        string(&config, "paths.data"),
+    ) {
+        (Some(examples), _) => PathBuf::from(examples),
+        (None, data) => Path::new(data.unwrap_or("data")).join("examples"),
+    }
+}
+
+fn string<'a>(values: &'a HashMap<String, Value>, key: &str) -> Option<&'a str> {
+    match values.get(key) {
+        Some(Value::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+/// One test per part with an expected answer of every `[[example]]` table, named after its file, e.g.
+/// `example_01_2_part_1` for `01-2.txt`. Examples without answers get a single test that reads them.
+fn tests(manifest: &str) -> String {
+    // NOTE: invalid manifests get a single test, which reports the error when it runs.
+    let values = parse_toml(manifest).unwrap_or_else(|_| {
+        HashMap::from([("example.0.file".to_string(), Value::String(String::new()))])
+    });
+
+    let names: Vec<String> = (0..)
+        .take_while(|index| {
+            values
+                .keys()
+                .any(|key| key.starts_with(&format!("example.{index}.")))
+        })
+        .map(
+            |index| match string(&values, &format!("example.{index}.file")) {
+                Some(file) if !file.is_empty() => {
+                    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+                    format!(
+                        "example_{}",
+                        stem.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                    )
+                }
+                _ => format!("example_{}", index + 1),
+            },
+        )
+        .collect();
+
+    let mut tests = String::new();
+    for (index, name) in names.iter().enumerate() {
+        // NOTE: the same file can be listed more than once.
+        let name = if names[..index].contains(name) {
+            format!("{name}_{}", index + 1)
+        } else {
+            name.clone()
+        };
+
+        let parts: Vec<u8> = (1..=2)
+            .filter(|part| values.contains_key(&format!("example.{index}.part_{part}")))
+            .collect();
+        if parts.is_empty() {
+            tests.push_str(&format!(
+                "#[test]\nfn {name}() {{\n    run_example({index}, None);\n}}\n"
+            ));
+        }
+        for part in parts {
+            tests.push_str(&format!(
+                "#[test]\nfn {name}_part_{part}() {{\n    run_example({index}, Some({part}));\n}}\n"
+            ));
+        }
+    }
+    tests
+}
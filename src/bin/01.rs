@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 advent_of_code::solution!(1);
+advent_of_code::example_tests!(1);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut list1, mut list2) = parse_two_lists(input);
@@ -47,20 +48,3 @@ fn parse_two_lists(input: &str) -> (Vec<u32>, Vec<u32>) {
     });
     (list1, list2)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
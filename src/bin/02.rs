@@ -2,6 +2,7 @@ use std::iter::Map;
 use std::str::Lines;
 
 advent_of_code::solution!(2);
+advent_of_code::example_tests!(2);
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
@@ -51,27 +52,10 @@ fn is_strictly_increasing(levels: &[u32]) -> bool {
     })
 }
 
-fn parse_to_iter(input: &str) -> Map<Lines, fn(&str) -> Vec<u32>> {
+fn parse_to_iter(input: &str) -> Map<Lines<'_>, fn(&str) -> Vec<u32>> {
     input.lines().map(|l| {
         l.split_whitespace()
             .filter_map(|w| w.parse::<u32>().ok())
             .collect::<Vec<_>>()
     })
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
-}
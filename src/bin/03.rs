@@ -1,6 +1,7 @@
 use regex::Regex;
 
 advent_of_code::solution!(3);
+advent_of_code::example_tests!(3);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mul_regex = Regex::new(r"mul\((?<left>[0-9]{1,3}),(?<right>[0-9]{1,3})\)").unwrap();
@@ -45,20 +46,3 @@ pub fn part_two(input: &str) -> Option<u32> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(48));
-    }
-}
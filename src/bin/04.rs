@@ -1,4 +1,5 @@
 advent_of_code::solution!(4);
+advent_of_code::example_tests!(4);
 
 #[derive(Clone, Eq, PartialEq)]
 enum Char {
@@ -101,7 +102,7 @@ pub fn part_two(_input: &str) -> Option<u32> {
     let possible_x_center_coords = (1..(char_matrix.len() - 1))
         .flat_map(|row_index| {
             (1..(char_matrix.len() - 1))
-                .map(move |col_index| (row_index.clone(), col_index))
+                .map(move |col_index| (row_index, col_index))
         })
         .filter(|(row_index, col_index)| char_matrix[*row_index][*col_index] == Char::A)
         .collect::<Vec<_>>();
@@ -123,20 +124,3 @@ pub fn part_two(_input: &str) -> Option<u32> {
             .count() as u32,
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
-    }
-}
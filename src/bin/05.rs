@@ -1,6 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(5);
+advent_of_code::example_tests!(5);
+advent_of_code::fuzz_target!(|input: &str| {
+    let (rules, pages) = input.split_once("\n\n")?;
+    Some((parse_ordering_rules(rules), parse_pages_to_produce(pages).collect::<Vec<_>>()))
+});
 
 pub fn part_one(input: &str) -> Option<u32> {
     let split = input.split("\n\n").collect::<Vec<&str>>();
@@ -22,7 +27,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             let mut updated_page_numbers = Vec::new();
             (0..page_numbers.len()).for_each(|_index| {
                 let (&&next_page_number, _) = scoped_rules.iter()
-                    .find(|(_, following_page_numbers)| following_page_numbers.len() == 0)
+                    .find(|(_, following_page_numbers)| following_page_numbers.is_empty())
                     .unwrap();
                 updated_page_numbers.push(next_page_number);
                 scoped_rules.remove(&next_page_number);
@@ -37,7 +42,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(calculate_middle_numbers_sum(updated_correctly_ordered_lines))
 }
 
-fn create_scoped_rules<'a, 'b>(rules: &'a HashMap<u32, HashSet<u32>>, page_numbers: &'a Vec<u32>, page_numbers_set: HashSet<u32>) -> HashMap<&'a u32, HashSet<u32>> {
+fn create_scoped_rules<'a>(rules: &'a HashMap<u32, HashSet<u32>>, page_numbers: &'a [u32], page_numbers_set: HashSet<u32>) -> HashMap<&'a u32, HashSet<u32>> {
     page_numbers.iter().map(|page_number| {
         let following_page_numbers = rules.get(page_number)
             .map(|following_page_numbers| {
@@ -47,7 +52,7 @@ fn create_scoped_rules<'a, 'b>(rules: &'a HashMap<u32, HashSet<u32>>, page_numbe
                     .filter(|page_number| page_numbers_set.contains(page_number));
                 HashSet::from_iter(filtered_relevant_page_numbers_iter)
             })
-            .unwrap_or(HashSet::new());
+            .unwrap_or_default();
         (page_number, following_page_numbers)
     })
         .collect::<HashMap<_, _>>()
@@ -75,7 +80,7 @@ fn parse_pages_to_produce(input: &str) -> impl Iterator<Item = Vec<u32>> + use<'
 fn is_page_correctly_ordered(page_numbers: &[u32], ordering_rules: &HashMap<u32, HashSet<u32>>) -> bool {
     let mut before_numbers: HashSet<u32> = HashSet::new();
     page_numbers.iter().all(|number| {
-        let rule_violation = match ordering_rules.get(&number) {
+        let rule_violation = match ordering_rules.get(number) {
             Some(forbidden_before) => forbidden_before.intersection(&before_numbers).count() > 0,
             None => false
         };
@@ -90,20 +95,3 @@ fn calculate_middle_numbers_sum(correctly_ordered_lines: Vec<Vec<u32>>) -> u32 {
         .collect::<Vec<_>>();
     middle_numbers.iter().sum()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
-    }
-}
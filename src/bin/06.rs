@@ -1,6 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::differential::Rng;
+use advent_of_code::template::Solution;
+use std::thread;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, Day06, solve);
+advent_of_code::example_tests!(6, Day06);
+advent_of_code::generator!(generate);
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
 enum Direction {
@@ -11,6 +15,8 @@ enum Direction {
 }
 
 impl Direction {
+    const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
     fn turn_right(self) -> Direction {
         match self {
             Direction::Up => Direction::Right,
@@ -19,146 +25,337 @@ impl Direction {
             Direction::Left => Direction::Up
         }
     }
-    
-    fn get_shift_coords(&self) -> (i32, i32) {
-        match self {
-            Direction::Up => (0, -1),
-            Direction::Right => (1, 0),
-            Direction::Down => (0, 1),
-            Direction::Left => (-1, 0)
-        }
-    }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map_size = input.lines().count() as i32;
-    let obstacle_coords = parse_obstacle_coords(input);
-    let starting_coords: (i32, i32) = parse_starting_coords(input);
-    
-    let walk_result = walk_until_outside_or_loop(
-        map_size,
-        &obstacle_coords,
-        starting_coords,
-        Direction::Up,
-    );
-    match walk_result {
-        WalkResult::OutsideMap(distinct_fields_visited) => Some(distinct_fields_visited.len() as u32),
-        WalkResult::Loop => None
-    }
-}
+struct Day06;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map_size = input.lines().count() as i32;
-    let obstacle_coords = parse_obstacle_coords(input);
-    let starting_coords: (i32, i32) = parse_starting_coords(input);
-
-    let walk_result = walk_until_outside_or_loop(
-        map_size,
-        &obstacle_coords,
-        starting_coords,
-        Direction::Up,
-    );
-
-    let mut distinct_fields_visited = match walk_result {
-        WalkResult::Loop => return None,
-        WalkResult::OutsideMap(distinct_fields_visited) => distinct_fields_visited,
-    };
+/// Marks fields from which the guard walks out of the lab in a jump table.
+const EXIT: u32 = u32::MAX;
 
-    // remove starting position (obstacle not allowed)
-    distinct_fields_visited.remove(&starting_coords);
-    let amount_of_obstacles_leading_to_loop = distinct_fields_visited.iter()
-        .filter(|&&coords_in_path| {
-            let mut obstacles_with_added_obstacle = obstacle_coords.iter().cloned().collect::<HashSet<_>>();
-            obstacles_with_added_obstacle.insert(coords_in_path);
-            
-            match walk_until_outside_or_loop(
-                map_size,
-                &obstacles_with_added_obstacle,
-                starting_coords,
-                Direction::Up,
-            ) {
-                WalkResult::OutsideMap(_) => false,
-                WalkResult::Loop => true,
-            }
-        })
-        .count() as u32;
-    Some(amount_of_obstacles_leading_to_loop)
+/// The lab as dense grid. Fields are indexed `y * width + x`.
+struct Lab {
+    width: usize,
+    height: usize,
+    obstacles: Vec<bool>,
+    start: usize,
+    /// For every direction and field, the last free field before the next obstacle in that direction,
+    /// or [`EXIT`] if there is none.
+    jumps: [Vec<u32>; 4],
 }
 
-enum WalkResult {
-    OutsideMap(HashSet<(i32, i32)>),
-    Loop
+/// The first step of the guard onto a field: the field it came from and the direction it faced.
+#[derive(Clone, Copy)]
+struct FirstEntry {
+    field: usize,
+    from: usize,
+    direction: Direction,
 }
 
-fn walk_until_outside_or_loop(
-    map_size: i32,
-    obstacle_coords: &HashSet<(i32, i32)>,
-    starting_coords: (i32, i32),
-    starting_direction: Direction) -> WalkResult {
-    let mut visited_coords: HashMap<(i32, i32), HashSet<Direction>> = HashMap::new();
-
-    let mut current_coords: (i32, i32) = starting_coords;
-    let mut current_direction = starting_direction;
-    let mut current_shift = current_direction.get_shift_coords();
-    while (0..map_size).contains(&current_coords.0) && (0..map_size).contains(&current_coords.1) {
-        if let Some(already_walked_directions) = visited_coords.get_mut(&current_coords) {
-            if already_walked_directions.contains(&current_direction) {
-                return WalkResult::Loop;
+/// Visited `(field, direction)` states of a walk.
+struct VisitedStates(Vec<u64>);
+
+impl VisitedStates {
+    fn new(fields: usize) -> Self {
+        VisitedStates(vec![0; (fields * 4).div_ceil(64)])
+    }
+
+    /// Marks a state as visited. Returns `false` if it was visited already.
+    fn insert(&mut self, field: usize, direction: Direction) -> bool {
+        let state = field * 4 + direction as usize;
+        let (word, bit) = (state / 64, 1 << (state % 64));
+        let is_new = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        is_new
+    }
+}
+
+impl Lab {
+    fn coords(&self, field: usize) -> (usize, usize) {
+        (field % self.width, field / self.width)
+    }
+
+    /// The neighbouring field in a direction, or `None` at the border of the lab.
+    fn neighbour(&self, field: usize, direction: Direction) -> Option<usize> {
+        let (x, y) = self.coords(field);
+        match direction {
+            Direction::Up => (y > 0).then(|| field - self.width),
+            Direction::Right => (x + 1 < self.width).then(|| field + 1),
+            Direction::Down => (y + 1 < self.height).then(|| field + self.width),
+            Direction::Left => (x > 0).then(|| field - 1),
+        }
+    }
+
+    fn compute_jumps(&mut self) {
+        let fields = self.width * self.height;
+        for direction in Direction::ALL {
+            let mut jumps = vec![EXIT; fields];
+            // NOTE: visit fields in the order of the walking direction reversed, so the jump of the
+            // neighbour ahead is known already.
+            let order: Box<dyn Iterator<Item = usize>> = match direction {
+                Direction::Up | Direction::Left => Box::new(0..fields),
+                Direction::Down | Direction::Right => Box::new((0..fields).rev()),
+            };
+            for field in order {
+                jumps[field] = match self.neighbour(field, direction) {
+                    None => EXIT,
+                    Some(ahead) if self.obstacles[ahead] => field as u32,
+                    Some(ahead) => jumps[ahead],
+                };
             }
-            already_walked_directions.insert(current_direction);
+            self.jumps[direction as usize] = jumps;
+        }
+    }
+
+    /// The last free field before the next obstacle, taking an added obstacle into account.
+    fn jump(&self, from: usize, direction: Direction, added_obstacle: usize) -> Option<usize> {
+        let stop = self.jumps[direction as usize][from];
+        let (x, y) = self.coords(from);
+        let (obstacle_x, obstacle_y) = self.coords(added_obstacle);
+        let ahead = match direction {
+            Direction::Up => obstacle_x == x && obstacle_y < y,
+            Direction::Right => obstacle_y == y && obstacle_x > x,
+            Direction::Down => obstacle_x == x && obstacle_y > y,
+            Direction::Left => obstacle_y == y && obstacle_x < x,
+        };
+        let distance = |field: usize| {
+            let (field_x, field_y) = self.coords(field);
+            field_x.abs_diff(x) + field_y.abs_diff(y)
+        };
+
+        if ahead && (stop == EXIT || distance(added_obstacle) <= distance(stop as usize)) {
+            self.neighbour(added_obstacle, direction.turn_right().turn_right())
         } else {
-            visited_coords.insert(current_coords, HashSet::from([current_direction]));
+            (stop != EXIT).then_some(stop as usize)
         }
+    }
 
-        let mut next_coords = (current_coords.0 + current_shift.0, current_coords.1 + current_shift.1);
-        while obstacle_coords.contains(&next_coords) {
-            current_direction = current_direction.turn_right();
-            current_shift = current_direction.get_shift_coords();
-            next_coords = (current_coords.0 + current_shift.0, current_coords.1 + current_shift.1);
+    /// Whether the guard loops with an obstacle added on the field of `entry`, starting right before
+    /// the guard would step onto it.
+    fn loops_with_obstacle(&self, entry: FirstEntry) -> bool {
+        let mut visited = VisitedStates::new(self.obstacles.len());
+        let mut field = entry.from;
+        let mut direction = entry.direction;
+
+        while let Some(stop) = self.jump(field, direction, entry.field) {
+            if !visited.insert(stop, direction) {
+                return true;
+            }
+            field = stop;
+            direction = direction.turn_right();
         }
-        current_coords = next_coords;
+        false
     }
-    WalkResult::OutsideMap(visited_coords.into_keys().collect())
 }
 
-fn parse_starting_coords(input: &str) -> (i32, i32) {
-    input.lines()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .find(|(_, info_char)| *info_char == '^')
-                .map(|(x, _)| (x as i32, y as i32))
-        })
-        .expect("There should be one instance of '^' in the input data")
+impl Solution for Day06 {
+    type Parsed = Lab;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Lab {
+        let width = input.lines().next().map_or(0, str::len);
+        let height = input.lines().count();
+        let fields: Vec<char> = input.lines().flat_map(str::chars).collect();
+        let mut lab = Lab {
+            width,
+            height,
+            obstacles: fields.iter().map(|field| *field == '#').collect(),
+            start: fields.iter()
+                .position(|field| *field == '^')
+                .expect("There should be one instance of '^' in the input data"),
+            jumps: Default::default(),
+        };
+        lab.compute_jumps();
+        lab
+    }
+
+    fn part_one(lab: &Lab) -> Option<u32> {
+        let first_entries = walk_guard_path(lab)?;
+        Some(first_entries.len() as u32 + 1)
+    }
+
+    fn part_two(lab: &Lab) -> Option<u32> {
+        let first_entries = walk_guard_path(lab)?;
+        Some(count_obstacles_leading_to_loop(lab, &first_entries))
+    }
+
+    /// Walks the guard path once and reuses it for both parts.
+    fn solve(lab: &Lab) -> (Option<u32>, Option<u32>) {
+        match walk_guard_path(lab) {
+            None => (None, None),
+            Some(first_entries) => (
+                Some(first_entries.len() as u32 + 1),
+                Some(count_obstacles_leading_to_loop(lab, &first_entries)),
+            ),
+        }
+    }
 }
 
-fn parse_obstacle_coords(input: &str) -> HashSet<(i32, i32)> {
-    let obstacle_coords: HashSet<(i32, i32)> = input.lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, info_char)| *info_char == '#')
-                .map(move |(x, _)| (x as i32, y as i32))
+/// Walks the guard field by field and returns the first entry onto every field except the start,
+/// or `None` if the guard loops.
+fn walk_guard_path(lab: &Lab) -> Option<Vec<FirstEntry>> {
+    let mut entered = vec![false; lab.obstacles.len()];
+    let mut visited = VisitedStates::new(lab.obstacles.len());
+    let mut first_entries = vec![];
+
+    let mut field = lab.start;
+    let mut direction = Direction::Up;
+    entered[field] = true;
+
+    while visited.insert(field, direction) {
+        let Some(next) = lab.neighbour(field, direction) else {
+            return Some(first_entries);
+        };
+        if lab.obstacles[next] {
+            direction = direction.turn_right();
+            continue;
+        }
+        if !entered[next] {
+            entered[next] = true;
+            first_entries.push(FirstEntry { field: next, from: field, direction });
+        }
+        field = next;
+    }
+    None
+}
+
+/// Tries an obstacle on every field of the guard path, split between the available cores.
+fn count_obstacles_leading_to_loop(lab: &Lab, first_entries: &[FirstEntry]) -> u32 {
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    count_obstacles_leading_to_loop_on_threads(lab, first_entries, threads)
+}
+
+fn count_obstacles_leading_to_loop_on_threads(lab: &Lab, first_entries: &[FirstEntry], threads: usize) -> u32 {
+    let threads = threads.max(1);
+    let chunk_size = first_entries.len().div_ceil(threads).max(1);
+    let count_loops = |entries: &[FirstEntry]| {
+        entries.iter().filter(|&&entry| lab.loops_with_obstacle(entry)).count() as u32
+    };
+
+    if threads == 1 {
+        return count_loops(first_entries);
+    }
+
+    thread::scope(|scope| {
+        first_entries.chunks(chunk_size)
+            .map(|entries| scope.spawn(move || count_loops(entries)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .sum()
+    })
+}
+
+/// A lab of `size` x `size` fields with obstacles on about every tenth field.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let start = rng.below((size * size) as u64) as usize;
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| match y * size + x {
+                    field if field == start => '^',
+                    _ if rng.one_in(10) => '#',
+                    _ => '.',
+                })
+                .collect::<String>()
         })
-        .collect();
-    obstacle_coords
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Differential;
+    use advent_of_code::variants;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_solve() {
+        let lab = Day06::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day06::solve(&lab);
+        assert_eq!(result, (Some(41), Some(6)));
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
+    fn test_count_obstacles_leading_to_loop_on_threads() {
+        let lab = Day06::parse(&advent_of_code::template::read_file("examples", DAY));
+        let first_entries = walk_guard_path(&lab).unwrap();
+        for threads in [1, 2, 3, 100] {
+            assert_eq!(count_obstacles_leading_to_loop_on_threads(&lab, &first_entries, threads), 6);
+        }
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+    fn test_solve_variants_agree() {
+        Differential::new(|rng, size| (rng.next_u64(), size as u64 + 1))
+            .max_size(12)
+            .check(&variants![
+                walk_every_trial => |&(seed, size)| solve_by_walking(&generate(seed, size as usize)),
+                single_thread => |&(seed, size)| solve_on_threads(&generate(seed, size as usize), 1),
+                four_threads => |&(seed, size)| solve_on_threads(&generate(seed, size as usize), 4),
+            ]);
+    }
+
+    fn solve_on_threads(input: &str, threads: usize) -> (Option<u32>, Option<u32>) {
+        let lab = Day06::parse(input);
+        match walk_guard_path(&lab) {
+            None => (None, None),
+            Some(first_entries) => (
+                Some(first_entries.len() as u32 + 1),
+                Some(count_obstacles_leading_to_loop_on_threads(&lab, &first_entries, threads)),
+            ),
+        }
+    }
+
+    /// Solves both parts by re-walking the whole path field by field for every added obstacle.
+    fn solve_by_walking(input: &str) -> (Option<u32>, Option<u32>) {
+        let size = input.lines().count() as i32;
+        let mut obstacles = HashSet::new();
+        let mut start = (0, 0);
+        for (y, line) in input.lines().enumerate() {
+            for (x, field) in line.chars().enumerate() {
+                match field {
+                    '#' => _ = obstacles.insert((x as i32, y as i32)),
+                    '^' => start = (x as i32, y as i32),
+                    _ => {}
+                }
+            }
+        }
+
+        let walk = |obstacles: &HashSet<(i32, i32)>| {
+            let mut visited: HashMap<(i32, i32), HashSet<(i32, i32)>> = HashMap::new();
+            let (mut position, mut shift) = (start, (0, -1));
+            while (0..size).contains(&position.0) && (0..size).contains(&position.1) {
+                if !visited.entry(position).or_default().insert(shift) {
+                    return None;
+                }
+                for _ in 0..4 {
+                    if obstacles.contains(&(position.0 + shift.0, position.1 + shift.1)) {
+                        shift = (-shift.1, shift.0);
+                    }
+                }
+                if obstacles.contains(&(position.0 + shift.0, position.1 + shift.1)) {
+                    // NOTE: boxed in by obstacles, the guard turns forever.
+                    return None;
+                }
+                position = (position.0 + shift.0, position.1 + shift.1);
+            }
+            Some(visited.into_keys().collect::<HashSet<_>>())
+        };
+
+        let Some(path) = walk(&obstacles) else {
+            return (None, None);
+        };
+        let loops = path.iter()
+            .filter(|&&field| field != start)
+            .filter(|&&field| {
+                let mut obstacles = obstacles.clone();
+                obstacles.insert(field);
+                walk(&obstacles).is_none()
+            })
+            .count();
+        (Some(path.len() as u32), Some(loops as u32))
     }
 }
@@ -1,4 +1,7 @@
+use advent_of_code::math::concat_digits;
+
 advent_of_code::solution!(7);
+advent_of_code::example_tests!(7);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let parsed_input = parse_input(input);
@@ -52,12 +55,12 @@ fn is_equation_solvable_with_concatenation(eq_result: &u64, eq_operands: &[u64])
         return
             is_equation_solvable_with_concatenation(eq_result, &[eq_operands[0] + eq_operands[1]])
                 || is_equation_solvable_with_concatenation(eq_result, &[eq_operands[0] * eq_operands[1]])
-                || is_equation_solvable_with_concatenation(eq_result, &[concatenate(eq_operands[0], eq_operands[1])]);
+                || is_equation_solvable_with_concatenation(eq_result, &[concat_digits(eq_operands[0], eq_operands[1])]);
     }
 
     is_equation_solvable_with_concatenation(eq_result, &[&[eq_operands[0] + eq_operands[1]], &eq_operands[2..]].concat())
         || is_equation_solvable_with_concatenation(eq_result, &[&[eq_operands[0] * eq_operands[1]], &eq_operands[2..]].concat())
-        || is_equation_solvable_with_concatenation(eq_result, &[&[concatenate(eq_operands[0], eq_operands[1])], &eq_operands[2..]].concat())
+        || is_equation_solvable_with_concatenation(eq_result, &[&[concat_digits(eq_operands[0], eq_operands[1])], &eq_operands[2..]].concat())
 }
 
 fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
@@ -81,24 +84,3 @@ fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
         })
         .collect::<Vec<_>>()
 }
-
-fn concatenate(operand_1: u64, operand_2: u64) -> u64 {
-    (operand_1.to_string() + &operand_2.to_string()).parse::<u64>().unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
-}
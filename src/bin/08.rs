@@ -1,7 +1,8 @@
 use std::collections::{HashMap, HashSet};
-use gcd::Gcd;
+use advent_of_code::math::gcd;
 
 advent_of_code::solution!(8);
+advent_of_code::example_tests!(8);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let antenna_map_size = input.lines().filter(|line| !line.is_empty()).count() as i32;
@@ -30,18 +31,17 @@ pub fn part_two(input: &str) -> Option<u32> {
     let mut found_antinodes = HashSet::<(i32, i32)>::new();
     antenna_map.iter().for_each(|(_freq, coords)| {
         let mut coords_for_frequency = coords.iter().collect::<HashSet<_>>();
-        coords.iter().enumerate().for_each(|(_, current_antenna_coords)| {
+        coords.iter().for_each(|current_antenna_coords| {
             let (x, y) = *current_antenna_coords;
             coords_for_frequency.remove(current_antenna_coords);
             coords_for_frequency
                 .iter()
-                .enumerate()
-                .for_each(|(_, &(other_x, other_y))| {
+                .for_each(|&(other_x, other_y)| {
                     found_antinodes.insert(*current_antenna_coords);
 
                     let diff_y = other_y - y;
                     let diff_x = other_x - x;
-                    let gcd = (diff_x.abs() as u32).gcd(diff_y.abs() as u32) as i32;
+                    let gcd = gcd(diff_x.unsigned_abs().into(), diff_y.unsigned_abs().into()) as i32;
 
                     let diff_y = diff_y / gcd;
                     let diff_x = diff_x / gcd;
@@ -81,20 +81,3 @@ fn parse_antenna_map(input: &str) -> HashMap<char, Vec<(i32, i32)>> {
     });
     antenna_map
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
-}
@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
+use advent_of_code::differential::Rng;
 
 advent_of_code::solution!(9);
+advent_of_code::example_tests!(9);
+advent_of_code::generator!(generate);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let first_line = input.lines().next().unwrap();
@@ -22,7 +25,7 @@ pub fn part_one(input: &str) -> Option<u64> {
                 });
             }
         });
-    while free_space_indexes.len() > 0 {
+    while !free_space_indexes.is_empty() {
         let last_block = file_system.pop().unwrap();
 
         let block_to_allocate = match last_block {
@@ -62,8 +65,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 (0..disk_map_number).for_each(|_| file_system.push(None));
             }
         });
-    while file_indexes.len() > 0 {
-        let (file_system_index, file_size, file_number) = file_indexes.pop().unwrap();
+    while let Some((file_system_index, file_size, file_number)) = file_indexes.pop() {
         let free_space = free_space_indexes.iter()
             .enumerate()
             .filter(|&(_, (free_space_file_system_index, _))| *free_space_file_system_index < file_system_index)
@@ -93,19 +95,13 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(checksum)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
+/// A disk map of `size` digits (rounded up to an odd number) with files of 1-9 blocks.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    (0..size / 2 * 2 + 1)
+        .map(|index| {
+            let blocks = if index % 2 == 0 { rng.between(1, 9) } else { rng.below(10) };
+            char::from_digit(blocks as u32, 10).unwrap()
+        })
+        .collect()
 }
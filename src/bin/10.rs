@@ -1,6 +1,7 @@
 use std::collections::{HashSet};
 
 advent_of_code::solution!(10);
+advent_of_code::example_tests!(10);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let topographic_map = parse_topographic_map(input);
@@ -69,30 +70,13 @@ fn find_reachable_peaks(topographic_map: &Vec<Vec<u8>>, y: usize, x: usize, mut
         .filter(|&(y, x)| topographic_map[y][x] == current_height + 1)
         .collect();
 
-    if next_positions_to_check.len() == 0 {
+    if next_positions_to_check.is_empty() {
         return already_found_reachable_peaks;
     }
 
     for next_position in next_positions_to_check {
-        already_found_reachable_peaks = find_reachable_peaks(&topographic_map, next_position.0, next_position.1, already_found_reachable_peaks);
+        already_found_reachable_peaks = find_reachable_peaks(topographic_map, next_position.0, next_position.1, already_found_reachable_peaks);
     }
 
     already_found_reachable_peaks
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
-}
@@ -1,28 +1,40 @@
 use std::collections::HashMap;
+use advent_of_code::math::split_digits;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11,
+    part_one: [recursive => part_one, memoized => part_one_memoized],
+    part_two: [memoized => part_two],
+);
+advent_of_code::example_tests!(11);
+advent_of_code::params!(part_one_blinks: u64 = 25, part_two_blinks: u64 = 75);
 
 pub fn part_one(input: &str) -> Option<u64> {
     let initial_stones = parse_stones(input);
-    const NUMBER_OF_BLINKS: u64 = 25;
+    let number_of_blinks = params().part_one_blinks;
     Some(initial_stones.iter()
-        .map(|stone_number| num_stones_after_blinks(*stone_number, NUMBER_OF_BLINKS))
+        .map(|stone_number| num_stones_after_blinks(*stone_number, number_of_blinks))
         .sum())
 }
 
+pub fn part_one_memoized(input: &str) -> Option<u64> {
+    Some(sum_stones_after_blinks_optimized(&parse_stones(input), params().part_one_blinks))
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
-    let initial_stones = parse_stones(input);
-    const NUMBER_OF_BLINKS: u64 = 75;
+    Some(sum_stones_after_blinks_optimized(&parse_stones(input), params().part_two_blinks))
+}
+
+fn sum_stones_after_blinks_optimized(initial_stones: &[u64], number_of_blinks: u64) -> u64 {
     let mut cache: HashMap<(u64, u64), u64> = HashMap::new();
-    Some(initial_stones.iter()
-        .map(|stone_number| num_stones_after_blinks_optimized(*stone_number, NUMBER_OF_BLINKS, &mut cache))
-        .sum())
+    initial_stones.iter()
+        .map(|stone_number| num_stones_after_blinks_optimized(*stone_number, number_of_blinks, &mut cache))
+        .sum()
 }
 
 fn parse_stones(input: &str) -> Vec<u64> {
     let initial_stones = input
         .split_whitespace()
-        .filter(|s| *s != "")
+        .filter(|s| !s.is_empty())
         .map(|stone| stone.parse::<u64>().unwrap())
         .collect::<Vec<_>>();
     initial_stones
@@ -37,12 +49,7 @@ fn num_stones_after_blinks(stone_number: u64, number_of_blinks: u64) -> u64 {
         return num_stones_after_blinks(1, number_of_blinks - 1);
     }
 
-    let log10 = stone_number.ilog10();
-    let has_even_number_of_digits = log10 % 2 == 1;
-    if has_even_number_of_digits {
-        let divisor: u64 = 10_u64.pow(log10 / 2 + 1);
-        let first_half = stone_number / divisor;
-        let second_half = stone_number % divisor;
+    if let Some((first_half, second_half)) = split_digits(stone_number) {
         let num_stones_first_half_after_blink = num_stones_after_blinks(first_half, number_of_blinks - 1);
         let num_stones_second_half_after_blink = num_stones_after_blinks(second_half, number_of_blinks - 1);
         let num_stones_after_blink = num_stones_first_half_after_blink + num_stones_second_half_after_blink;
@@ -67,12 +74,7 @@ fn num_stones_after_blinks_optimized(stone_number: u64, num_blinks: u64, cache:
         return num_stones;
     }
 
-    let log10 = stone_number.ilog10();
-    let has_even_number_of_digits = log10 % 2 == 1;
-    if has_even_number_of_digits {
-        let divisor: u64 = 10_u64.pow(log10 / 2 + 1);
-        let first_half = stone_number / divisor;
-        let second_half = stone_number % divisor;
+    if let Some((first_half, second_half)) = split_digits(stone_number) {
         let second_half_num_stones = num_stones_after_blinks_optimized(first_half, num_blinks - 1, cache);
         let first_half_num_stones = num_stones_after_blinks_optimized(second_half, num_blinks - 1, cache);
         let num_stones = second_half_num_stones + first_half_num_stones;
@@ -88,6 +90,8 @@ fn num_stones_after_blinks_optimized(stone_number: u64, num_blinks: u64, cache:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Differential;
+    use advent_of_code::variants;
 
     #[test]
     fn test_num_stones() {
@@ -117,14 +121,12 @@ mod tests {
     }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
+    fn test_num_stones_variants_agree() {
+        Differential::new(|rng, size| (rng.below(10u64.pow(size as u32 / 2)), rng.below(size as u64 + 1)))
+            .max_size(20)
+            .check(&variants![
+                recursive => |&(stone, blinks)| num_stones_after_blinks(stone, blinks),
+                optimized => |&(stone, blinks)| num_stones_after_blinks_optimized(stone, blinks, &mut HashMap::new()),
+            ]);
     }
 }
@@ -1,6 +1,9 @@
+use advent_of_code::template::phase::phase;
+use advent_of_code::template::Solution;
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, Day12);
+advent_of_code::example_tests!(12, Day12);
 
 #[derive(Debug)]
 struct Region {
@@ -81,7 +84,7 @@ impl Region {
             })
             .fold(HashMap::new(), |mut acc: HashMap<(Direction, i32), Vec<i32>>, (direction_with_coord_along, orthogonal_coord)| {
                 acc.entry(direction_with_coord_along)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(orthogonal_coord);
                 acc
             })
@@ -113,26 +116,33 @@ impl Region {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let map: Vec<Vec<char>> = parse_map(input);
-    let regions = get_regions(map);
+struct Day12;
 
-    let price = regions
-        .iter()
-        .map(|r| r.price() as u64)
-        .sum();
-    Some(price)
-}
+impl Solution for Day12 {
+    type Parsed = Vec<Region>;
+    type Output1 = u64;
+    type Output2 = u64;
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let map: Vec<Vec<char>> = parse_map(input);
-    let regions = get_regions(map);
+    fn parse(input: &str) -> Vec<Region> {
+        let map: Vec<Vec<char>> = phase("parse_map", || parse_map(input));
+        phase("get_regions", || get_regions(map))
+    }
 
-    let price = regions
-        .iter()
-        .map(|r| r.discounted_price() as u64)
-        .sum();
-    Some(price)
+    fn part_one(regions: &Vec<Region>) -> Option<u64> {
+        let price = regions
+            .iter()
+            .map(|r| r.price() as u64)
+            .sum();
+        Some(price)
+    }
+
+    fn part_two(regions: &Vec<Region>) -> Option<u64> {
+        let price = regions
+            .iter()
+            .map(|r| r.discounted_price() as u64)
+            .sum();
+        Some(price)
+    }
 }
 
 fn get_regions(map: Vec<Vec<char>>) -> Vec<Region> {
@@ -194,18 +204,6 @@ fn parse_map(input: &str) -> Vec<Vec<char>> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1930));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
-    }
-
     #[test]
     fn test_get_regions() {
         let result = get_regions(parse_map(&advent_of_code::template::read_file("examples", DAY)));
@@ -1,6 +1,14 @@
+use advent_of_code::math::{min_cost_nonnegative_solution, solve_linear_2x2, LinearSolution};
 use regex::Regex;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13,
+    part_one: [brute_force => part_one, optimized => part_one_optimized],
+    part_two: [optimized => part_two],
+);
+advent_of_code::example_tests!(13);
+
+const BUTTON_A_TOKEN_COST: u64 = 3;
+const BUTTON_B_TOKEN_COST: u64 = 1;
 
 struct ClawMachine {
     button_a_dx: u64,
@@ -21,32 +29,34 @@ impl ClawMachine {
         let ba_dy = self.button_a_dy as i64;
         let bb_dx = self.button_b_dx as i64;
         let bb_dy = self.button_b_dy as i64;
-        let b_numerator = p_y * ba_dx - ba_dy * p_x;
-        let b_denominator = ba_dx * bb_dy - ba_dy * bb_dx;
-
-        if b_denominator == 0 {
-            // luckily this case is not in the input data ;)
-            panic!("This case is not covered. But this case could be solvable with multiple solutions (one or multiple cheapest) or could also not be solvable");
-        }
-
-        if b_numerator % b_denominator != 0 {
-            return None
-        }
-
-        let b = b_numerator / b_denominator;
 
-        let remaining_dx_to_prize = p_x - b * bb_dx;
-        if remaining_dx_to_prize % ba_dx != 0 {
-            return None
-        }
-
-        let a = remaining_dx_to_prize / ba_dx;
+        let (a, b) = match solve_linear_2x2([ba_dx, bb_dx, p_x], [ba_dy, bb_dy, p_y]) {
+            LinearSolution::Inconsistent => return None,
+            LinearSolution::Unique(a, b) => (a.to_integer()?, b.to_integer()?),
+            LinearSolution::Infinite => {
+                // both buttons move the claw along the same line as the prize:
+                // pick the cheapest combination that reaches the prize on one axis.
+                let (a_delta, b_delta, prize) = if ba_dx != 0 || bb_dx != 0 {
+                    (ba_dx, bb_dx, p_x)
+                } else {
+                    (ba_dy, bb_dy, p_y)
+                };
+                let (a, b) = min_cost_nonnegative_solution(
+                    a_delta,
+                    b_delta,
+                    prize,
+                    BUTTON_A_TOKEN_COST as i64,
+                    BUTTON_B_TOKEN_COST as i64,
+                )?;
+                (a as i128, b as i128)
+            }
+        };
 
         if a < 0 || b < 0 {
            return None;
         }
 
-        Some(a as u64 * 3 + b as u64)
+        Some(a as u64 * BUTTON_A_TOKEN_COST + b as u64 * BUTTON_B_TOKEN_COST)
     }
 }
 
@@ -58,25 +68,36 @@ impl ClawMachine {
                 let pos_x_after_button_a_presses = self.button_a_dx * num_button_a_presses;
                 let remaining_dx_to_price = self.prize_pos_x - pos_x_after_button_a_presses;
 
-                let is_remaining_dx_divisible_by_button_b_dx = remaining_dx_to_price % self.button_b_dx == 0;
-                if !is_remaining_dx_divisible_by_button_b_dx {
-                    return None;
-                }
-                let num_button_b_presses = remaining_dx_to_price / self.button_b_dx;
+                let num_button_b_presses = if self.button_b_dx == 0 {
+                    // button B does not move along x, so its presses follow from the remaining dy.
+                    if remaining_dx_to_price != 0 {
+                        return None;
+                    }
+                    let remaining_dy_to_price = self.prize_pos_y.checked_sub(self.button_a_dy * num_button_a_presses)?;
+                    match self.button_b_dy {
+                        0 => 0,
+                        button_b_dy if remaining_dy_to_price.is_multiple_of(button_b_dy) => remaining_dy_to_price / button_b_dy,
+                        _ => return None,
+                    }
+                } else {
+                    let is_remaining_dx_divisible_by_button_b_dx = remaining_dx_to_price.is_multiple_of(self.button_b_dx);
+                    if !is_remaining_dx_divisible_by_button_b_dx {
+                        return None;
+                    }
+                    remaining_dx_to_price / self.button_b_dx
+                };
                 if num_button_b_presses > 100 {
                     return None;
                 }
-                return Some((num_button_a_presses, num_button_b_presses));
+                Some((num_button_a_presses, num_button_b_presses))
             })
             .filter(|(num_button_a_presses, num_button_b_presses)| {
                 self.prize_pos_y == self.button_a_dy * num_button_a_presses + self.button_b_dy * num_button_b_presses
             })
-            .next()
             .map(|(num_button_a_presses, num_button_b_presses)| {
-                const BUTTON_A_TOKEN_COST: u64 = 3;
-                const BUTTON_B_TOKEN_COST: u64 = 1;
                 num_button_a_presses * BUTTON_A_TOKEN_COST + num_button_b_presses * BUTTON_B_TOKEN_COST
             })
+            .min()
     }
 }
 
@@ -90,6 +111,16 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(min_total_tokens_to_spent)
 }
 
+pub fn part_one_optimized(input: &str) -> Option<u64> {
+    let claw_machines = parse_claw_machines(input);
+
+    let min_total_tokens_to_spent: u64 = claw_machines.iter()
+        .filter_map(|machine| machine.min_tokens_to_price_optimized())
+        .sum();
+
+    Some(min_total_tokens_to_spent)
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let mut claw_machines = parse_claw_machines(input);
     claw_machines.iter_mut()
@@ -113,14 +144,14 @@ Prize: X=(?<prize_x>[0-9]+), Y=(?<prize_y>[0-9]+)").unwrap();
     let claw_machines: Vec<ClawMachine> = claw_machine_regex
         .captures_iter(input)
         .map(|captures| {
-            return ClawMachine {
+            ClawMachine {
                 button_a_dx: captures.name("button_a_x").unwrap().as_str().parse().unwrap(),
                 button_a_dy: captures.name("button_a_y").unwrap().as_str().parse().unwrap(),
                 button_b_dx: captures.name("button_b_x").unwrap().as_str().parse().unwrap(),
                 button_b_dy: captures.name("button_b_y").unwrap().as_str().parse().unwrap(),
                 prize_pos_x: captures.name("prize_x").unwrap().as_str().parse().unwrap(),
                 prize_pos_y: captures.name("prize_y").unwrap().as_str().parse().unwrap(),
-            };
+            }
         })
         .collect();
     claw_machines
@@ -129,6 +160,21 @@ Prize: X=(?<prize_x>[0-9]+), Y=(?<prize_y>[0-9]+)").unwrap();
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Differential;
+    use advent_of_code::variants;
+
+    /// A claw machine from `[button_a_dx, button_a_dy, button_b_dx, button_b_dy, a_presses, b_presses]`,
+    /// with the prize at the position reached by pressing the buttons.
+    fn claw_machine_reached_by(&[a_dx, a_dy, b_dx, b_dy, a_presses, b_presses]: &[u64; 6]) -> ClawMachine {
+        ClawMachine {
+            button_a_dx: a_dx,
+            button_a_dy: a_dy,
+            button_b_dx: b_dx,
+            button_b_dy: b_dy,
+            prize_pos_x: a_presses * a_dx + b_presses * b_dx,
+            prize_pos_y: a_presses * a_dy + b_presses * b_dy,
+        }
+    }
 
     #[test]
     fn test_claw_machine_min_tokens_to_price() {
@@ -187,7 +233,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn test_claw_machine_min_tokens_to_price_optimized_edge_case_not_solvable() {
         let claw_machine = ClawMachine {
             button_a_dx: 4,
@@ -202,7 +247,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn test_claw_machine_min_tokens_to_price_optimized_edge_case_solvable() {
         let claw_machine = ClawMachine {
             button_a_dx: 4,
@@ -217,7 +261,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn test_claw_machine_min_tokens_to_price_optimized_edge_case_solvable_non_trivial() {
         let claw_machine = ClawMachine {
             button_a_dx: 6,
@@ -231,12 +274,6 @@ mod tests {
         assert_eq!(result, Some(6));
     }
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
-    }
-
     #[test]
     fn test_min_tokens_to_price_optimized_part_one_examples() {
         let claw_machines = parse_claw_machines(&advent_of_code::template::read_file("examples", DAY));
@@ -247,4 +284,59 @@ mod tests {
 
         assert_eq!(result, 480);
     }
+
+    #[test]
+    fn test_min_tokens_to_price_variants_agree() {
+        // with at most 25 presses per button, the cheapest solution never needs more than the
+        // 100 presses `min_tokens_to_price` is limited to, even if both buttons move along the same line.
+        Differential::new(|rng, size| {
+            let max_delta = size as u64 + 1;
+            let (dx, dy) = (rng.between(1, max_delta), rng.between(1, max_delta));
+            let [a_dx, a_dy, b_dx, b_dy] = if rng.one_in(3) {
+                // buttons moving along the same line reach the prize with many combinations
+                let (a_factor, b_factor) = (rng.between(1, max_delta), rng.between(1, max_delta));
+                [dx * a_factor, dy * a_factor, dx * b_factor, dy * b_factor]
+            } else {
+                [dx, dy, rng.between(1, max_delta), rng.between(1, max_delta)]
+            };
+            // button B sometimes moves along y only
+            let b_dx = if rng.one_in(5) { 0 } else { b_dx };
+            [a_dx, a_dy, b_dx, b_dy, rng.below(26), rng.below(26)]
+        })
+        .assume(|&[a_dx, a_dy, _, b_dy, a_presses, b_presses]| {
+            a_dx > 0 && a_dy > 0 && b_dy > 0 && a_presses <= 25 && b_presses <= 25
+        })
+        .check(&variants![
+            brute_force => |machine| claw_machine_reached_by(machine).min_tokens_to_price(),
+            optimized => |machine| claw_machine_reached_by(machine).min_tokens_to_price_optimized(),
+        ]);
+    }
+
+    #[test]
+    fn test_claw_machine_min_tokens_to_price_collinear_buttons() {
+        let claw_machine = ClawMachine {
+            button_a_dx: 4,
+            button_a_dy: 4,
+            button_b_dx: 1,
+            button_b_dy: 1,
+            prize_pos_x: 4,
+            prize_pos_y: 4,
+        };
+        let result = claw_machine.min_tokens_to_price();
+        assert_eq!(result, Some(3));
+    }
+
+    #[test]
+    fn test_claw_machine_min_tokens_to_price_button_b_without_dx() {
+        let claw_machine = ClawMachine {
+            button_a_dx: 2,
+            button_a_dy: 1,
+            button_b_dx: 0,
+            button_b_dy: 3,
+            prize_pos_x: 4,
+            prize_pos_y: 8,
+        };
+        assert_eq!(claw_machine.min_tokens_to_price(), Some(8));
+        assert_eq!(claw_machine.min_tokens_to_price_optimized(), Some(8));
+    }
 }
@@ -1,7 +1,14 @@
+use advent_of_code::aoc_debug;
+use advent_of_code::emergence::{Detector, Occupancy, Search};
+use advent_of_code::math::{crt, lcm};
 use regex::Regex;
-use std::collections::HashSet;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14,
+    part_one: [simulation => part_one],
+    part_two: [crt => part_two, search => part_two_search],
+);
+advent_of_code::example_tests!(14);
+advent_of_code::params!(width: usize = 101, height: usize = 103);
 
 #[derive(Clone)]
 struct Robot {
@@ -66,114 +73,90 @@ impl BathRoom {
         num_robots_in_q1 * num_robots_in_q2 * num_robots_in_q3 * num_robots_in_q4
     }
 
-    fn print(&self) {
-        let bathroom = self.to_occupation_grid();
-
-        let bathroom_string = bathroom.iter().fold(String::new(), |string, row| {
-            string
-                + &row
-                    .iter()
-                    .map(|cell| if *cell { "██" } else { "  " })
-                    .collect::<String>()
-                + "\n"
-        });
-        println!("{}", bathroom_string);
-    }
-
-    fn to_occupation_grid(&self) -> Vec<Vec<bool>> {
-        let mut bathroom = vec![vec![false; self.width]; self.height];
-        self.robots.iter().for_each(|robot| {
-            bathroom[robot.y][robot.x] = true;
-        });
-        bathroom
-    }
-
-    fn connected_components_count(&self) -> usize {
-        let mut remaining_components: HashSet<(usize, usize)> =
-            self.robots.iter().map(|r| (r.y, r.x)).collect();
-        let mut connected_components_count: usize = 0;
-        while !remaining_components.is_empty() {
-            let first_robot_in_component = remaining_components.iter().cloned().next().unwrap();
-            remaining_components.remove(&first_robot_in_component);
-            connected_components_count += 1;
-            let mut robots_in_component: HashSet<(usize, usize)> =
-                HashSet::from([first_robot_in_component]);
-            let all_directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-            while !robots_in_component.is_empty() {
-                let (current_y, current_x) = robots_in_component.iter().cloned().next().unwrap();
-                robots_in_component.remove(&(current_y, current_x));
-                all_directions
-                    .iter()
-                    .map(|(dy, dx)| (current_y as i32 + dy, current_x as i32 + dx))
-                    .filter(|&(dy, dx)| dy >= 0 && dx >= 0)
-                    .map(|(dy, dx)| (dy as usize, dx as usize))
-                    .filter(|robot| remaining_components.remove(robot))
-                    .for_each(|robot| {
-                        robots_in_component.insert(robot);
-                    });
-            }
-        }
-        connected_components_count
+    /// The step at which the robots are closest together along one axis. Positions along the x axis
+    /// repeat every `width` seconds and along the y axis every `height` seconds, so it is enough to
+    /// check one period of the axis.
+    fn min_variance_step(&self, axis: impl Fn(&Robot) -> (usize, i64), size: usize) -> usize {
+        (0..size)
+            .min_by_key(|&seconds| {
+                let positions = self.robots.iter().map(|robot| {
+                    let (position, velocity) = axis(robot);
+                    (position as i64 + velocity * seconds as i64).rem_euclid(size as i64)
+                });
+                spread(positions)
+            })
+            .unwrap_or_default()
     }
 
-    fn is_christmas_tree_arranged_by_checking_connected_component_count(&self) -> bool {
-        let connected_components_count = self.connected_components_count();
-        let max_connected_components = self.robots.len() - 350;
-        connected_components_count <= max_connected_components
+    /// The first step at which the robots are closest together on both axes, combined from the steps
+    /// of each axis with the Chinese remainder theorem.
+    fn min_variance_step_on_both_axes(&self) -> Option<u64> {
+        let x = self.min_variance_step(|robot| (robot.x, robot.dx), self.width);
+        let y = self.min_variance_step(|robot| (robot.y, robot.dy), self.height);
+        crt(&[(x as i64, self.width as i64), (y as i64, self.height as i64)])
+            .map(|(seconds, _)| seconds as u64)
     }
 
-    fn is_christmas_tree_arranged_by_checking_for_rectangle_side(&self) -> bool {
-        let occupation_grid = self.to_occupation_grid();
-        occupation_grid.iter().any(|row| {
-            let (max_consecutive_robots, _): (usize, usize) = row.iter()
-                .fold((0, 0), |(max, current), is_robot_present| {
-                    return if *is_robot_present {
-                        let next = current + 1;
-                        let next_max = if next > max { next } else { max };
-                        (next_max, next)
-                    } else {
-                        (max, 0)
-                    }
-                });
-            
-            max_consecutive_robots >= 31
-        })
+    fn occupancy(&self) -> Occupancy {
+        Occupancy::from_points(
+            self.width,
+            self.height,
+            self.robots.iter().map(|robot| (robot.x, robot.y)),
+        )
     }
 }
 
-const BATHROOM_WIDTH: usize = 101;
-const BATHROOM_HEIGHT: usize = 103;
-
 pub fn part_one(input: &str) -> Option<u64> {
     let robots = parse_robots(input);
+    let Params { width, height } = params();
     let mut bathroom = BathRoom {
         robots,
-        width: BATHROOM_WIDTH,
-        height: BATHROOM_HEIGHT,
+        width,
+        height,
     };
     bathroom.simulate(100);
     Some(bathroom.safety_factor())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut bathroom = BathRoom {
+    let Params { width, height } = params();
+    let bathroom = BathRoom {
         robots: parse_robots(input),
-        width: BATHROOM_WIDTH,
-        height: BATHROOM_HEIGHT,
+        width,
+        height,
     };
-    let mut seconds_passed: u32 = 0;
-    loop {
-        bathroom.simulate(1);
-        seconds_passed += 1;
+    bathroom
+        .min_variance_step_on_both_axes()
+        .map(|seconds| seconds as u32)
+}
 
+pub fn part_two_search(input: &str) -> Option<u32> {
+    let Params { width, height } = params();
+    let mut bathroom = BathRoom {
+        robots: parse_robots(input),
+        width,
+        height,
+    };
+    // NOTE: all robots are back at their start after lcm(width, height) seconds.
+    let period = lcm(width as u64, height as u64) as usize;
+    let candidates = Search::new(Detector::LargestComponent, 0..period)
+        .top(3)
+        .run(|_| {
+            let frame = bathroom.occupancy();
+            bathroom.simulate(1);
+            frame
+        });
+    candidates.iter().for_each(|candidate| aoc_debug!("{candidate}"));
 
-        if bathroom.is_christmas_tree_arranged_by_checking_for_rectangle_side() {
-            break;
-        }
-    }
-    bathroom.print();
+    candidates.first().map(|candidate| candidate.step as u32)
+}
 
-    Some(seconds_passed)
+/// `n²` times the variance of the positions, which keeps the comparison in integers.
+fn spread(positions: impl Iterator<Item = i64>) -> i64 {
+    let (n, sum, sum_of_squares) = positions.fold((0, 0, 0), |(n, sum, squares), position| {
+        (n + 1, sum + position, squares + position * position)
+    });
+    n * sum_of_squares - sum * sum
 }
 
 fn parse_robots(input: &str) -> Vec<Robot> {
@@ -193,19 +176,7 @@ fn parse_robots(input: &str) -> Vec<Robot> {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let robots = parse_robots(&advent_of_code::template::read_file("examples", DAY));
-        let mut bathroom = BathRoom {
-            robots,
-            width: 11,
-            height: 7,
-        };
-        bathroom.simulate(100);
-        let result = bathroom.safety_factor();
-        assert_eq!(result, 12);
-    }
+    use advent_of_code::differential::Rng;
 
     #[test]
     #[ignore]
@@ -215,28 +186,36 @@ mod tests {
     }
 
     #[test]
-    fn test_is_christmas_tree_arranged_by_checking_connected_component_count() {
-        let robots = parse_robots(&advent_of_code::template::read_file("inputs", DAY));
-        let mut bathroom = BathRoom {
-            robots,
-            width: BATHROOM_WIDTH,
-            height: BATHROOM_HEIGHT,
-        };
-        bathroom.simulate(7861);
-        let result = bathroom.is_christmas_tree_arranged_by_checking_connected_component_count();
-        assert_eq!(result, true);
+    fn test_part_two_finds_picture() {
+        let input = picture_input(4321, &mut Rng::new(14));
+        assert_eq!(part_two(&input), Some(4321));
+        assert_eq!(part_two_search(&input), Some(4321));
     }
 
     #[test]
-    fn test_is_christmas_tree_arranged_by_checking_for_rectangle_side() {
-        let robots = parse_robots(&advent_of_code::template::read_file("inputs", DAY));
-        let mut bathroom = BathRoom {
-            robots,
-            width: BATHROOM_WIDTH,
-            height: BATHROOM_HEIGHT,
-        };
-        bathroom.simulate(7861);
-        let result = bathroom.is_christmas_tree_arranged_by_checking_for_rectangle_side();
-        assert_eq!(result, true);
+    fn test_part_two_finds_picture_at_any_step() {
+        let mut rng = Rng::new(49);
+        for seconds in [0, 1, 100, 102, 5000, 10402] {
+            assert_eq!(part_two(&picture_input(seconds, &mut rng)), Some(seconds as u32));
+        }
+    }
+
+    /// Robots that form a triangle after `seconds` seconds, among robots that move randomly.
+    fn picture_input(seconds: i64, rng: &mut Rng) -> String {
+        let (width, height) = (101, 103);
+        let triangle = (0..15).flat_map(|row| (14 - row..15 + row).map(move |x| (40 + x, 30 + row)));
+        let noise: Vec<(i64, i64)> = (0..300)
+            .map(|_| (rng.below(width as u64) as i64, rng.below(height as u64) as i64))
+            .collect();
+
+        triangle
+            .chain(noise)
+            .map(|(x, y)| {
+                let (dx, dy) = (rng.between(1, 200) as i64 - 100, rng.between(1, 200) as i64 - 100);
+                let start_x = (x - dx * seconds).rem_euclid(width);
+                let start_y = (y - dy * seconds).rem_euclid(height);
+                format!("p={start_x},{start_y} v={dx},{dy}\n")
+            })
+            .collect()
     }
 }
@@ -4,6 +4,11 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 advent_of_code::solution!(15);
+advent_of_code::fuzz_target!(|input: &str| {
+    let (warehouse, movements) = input.split_once("\n\n")?;
+    Some((warehouse.parse::<Warehouse>(), movements.parse::<RobotMovements>()))
+});
+advent_of_code::example_tests!(15);
 
 #[derive(Copy, Clone)]
 enum Direction {
@@ -39,7 +44,7 @@ impl Display for Direction {
             Direction::Left => '<',
             Direction::Right => '>',
         };
-        write!(f, "{}", char.to_string())
+        write!(f, "{}", char)
     }
 }
 
@@ -102,7 +107,7 @@ impl Display for WareHouseObstacle {
             WareHouseObstacle::Wall => '#',
             WareHouseObstacle::Box => 'O',
         };
-        write!(f, "{}", char.to_string())
+        write!(f, "{}", char)
     }
 }
 
@@ -189,7 +194,7 @@ impl FromStr for Warehouse {
             .lines()
             .map(|line| {
                 line.chars()
-                    .map(|char| match char.clone() {
+                    .map(|char| match char {
                         '@' => WareHouseObstacle::None,
                         non_robot_char => WareHouseObstacle::from(non_robot_char),
                     })
@@ -227,7 +232,7 @@ impl Display for Warehouse {
                 if self.robot.x == x && self.robot.y == y {
                     write!(f, "@")?;
                 } else {
-                    write!(f, "{}", obstacle.to_string())?;
+                    write!(f, "{}", obstacle)?;
                 }
             }
             writeln!(f)?;
@@ -264,7 +269,7 @@ impl Display for LargeWareHouseObstacle {
             LargeWareHouseObstacle::Box(false) => '[',
             LargeWareHouseObstacle::Box(true) => ']',
         };
-        write!(f, "{}", char.to_string())
+        write!(f, "{}", char)
     }
 }
 
@@ -433,7 +438,7 @@ impl FromStr for LargeWarehouse {
             .lines()
             .map(|line| {
                 line.chars()
-                    .map(|char| match char.clone() {
+                    .map(|char| match char {
                         '@' => LargeWareHouseObstacle::None,
                         non_robot_char => LargeWareHouseObstacle::from(non_robot_char),
                     })
@@ -470,7 +475,7 @@ impl Display for LargeWarehouse {
                 if self.robot.x == x && self.robot.y == y {
                     write!(f, "@")?;
                 } else {
-                    write!(f, "{}", obstacle.to_string())?;
+                    write!(f, "{}", obstacle)?;
                 }
             }
             writeln!(f)?;
@@ -508,20 +513,3 @@ pub fn part_two(input: &str) -> Option<u64> {
     warehouse.simulate_robot_movements(movements);
     Some(warehouse.sum_box_coords())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(10092));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9021));
-    }
-}
@@ -0,0 +1,235 @@
+/// Cycle detection for simulations that are driven by a state transition function.
+///
+/// All functions treat `initial` as the state after `0` steps and `step` as the transition
+/// from one state to the next. A [`Cycle`] then describes the index of the first state that
+/// repeats (`start`) and the distance to its next occurrence (`length`).
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The cycle of a sequence of states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// Number of steps until the cycle is entered for the first time.
+    pub start: usize,
+    /// Number of steps it takes to get back to a state once inside the cycle.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps an arbitrary step count to the smallest step count that yields the same state.
+    ///
+    /// ```
+    /// # use advent_of_code::cycle::Cycle;
+    /// let cycle = Cycle { start: 3, length: 4 };
+    /// assert_eq!(cycle.equivalent_step(2), 2);
+    /// assert_eq!(cycle.equivalent_step(1_000_000_003), 3);
+    /// ```
+    #[must_use]
+    pub fn equivalent_step(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's "tortoise and hare" algorithm. Uses constant memory, but evaluates `step` about
+/// three times as often as [`find_cycle`].
+/// Loops forever if the sequence never repeats.
+pub fn floyd<T: PartialEq + Clone>(initial: T, step: impl Fn(&T) -> T) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Uses constant memory and usually fewer evaluations of `step` than [`floyd`].
+/// Loops forever if the sequence never repeats.
+pub fn brent<T: PartialEq + Clone>(initial: T, step: impl Fn(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Hash-based cycle detection. Evaluates `step` exactly `start + length` times, but keeps
+/// every visited state in memory.
+/// Loops forever if the sequence never repeats.
+pub fn find_cycle<T: Hash + Eq + Clone>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    try_find_cycle(initial, |state| Some(step(state)))
+        .expect("a total transition function can't terminate")
+}
+
+/// Hash-based cycle detection for simulations that can terminate, e.g. a guard walking off the map.
+/// Returns [`None`] if `step` returns [`None`] before a state repeats.
+pub fn try_find_cycle<T: Hash + Eq + Clone>(
+    initial: T,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Option<Cycle> {
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut state = initial;
+    let mut index = 0;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+            });
+        }
+        let next = step(&state)?;
+        seen.insert(state, index);
+        state = next;
+        index += 1;
+    }
+}
+
+/// Returns the state after `steps` steps, skipping whole cycles once the sequence starts repeating.
+///
+/// ```
+/// # use advent_of_code::cycle::state_after;
+/// assert_eq!(state_after(0_u32, |x| (x + 1) % 7, 1_000_000_000), 1_000_000_000 % 7);
+/// ```
+pub fn state_after<T: Hash + Eq + Clone>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+    steps: usize,
+) -> T {
+    let mut history: Vec<T> = vec![];
+    let mut seen: HashMap<T, usize> = HashMap::new();
+    let mut state = initial;
+
+    for index in 0..steps {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(steps));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), index);
+        history.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, find_cycle, floyd, state_after, try_find_cycle, Cycle};
+
+    // x -> x² + 1 (mod 255), starting at 3: 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+    fn pseudo_random(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn detects_cycles_with_prefix() {
+        let expected = Cycle {
+            start: 2,
+            length: 6,
+        };
+        assert_eq!(floyd(3, pseudo_random), expected);
+        assert_eq!(brent(3, pseudo_random), expected);
+        assert_eq!(find_cycle(3, pseudo_random), expected);
+    }
+
+    #[test]
+    fn detects_pure_cycles() {
+        let expected = Cycle {
+            start: 0,
+            length: 7,
+        };
+        let step = |x: &u32| (x + 3) % 7;
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_cycle(0, step), expected);
+    }
+
+    #[test]
+    fn detects_fixed_points() {
+        let expected = Cycle {
+            start: 3,
+            length: 1,
+        };
+        let step = |x: &u32| x.saturating_sub(1);
+        assert_eq!(floyd(3, step), expected);
+        assert_eq!(brent(3, step), expected);
+        assert_eq!(find_cycle(3, step), expected);
+    }
+
+    #[test]
+    fn handles_terminating_simulations() {
+        assert_eq!(try_find_cycle(0_u32, |x| (*x < 10).then_some(x + 1)), None);
+        assert_eq!(
+            try_find_cycle(0_u32, |x| Some((x + 1) % 4)),
+            Some(Cycle {
+                start: 0,
+                length: 4
+            })
+        );
+    }
+
+    #[test]
+    fn extrapolates_states() {
+        let cycle = Cycle {
+            start: 2,
+            length: 6,
+        };
+        for steps in [0, 1, 2, 7, 8, 9, 1000, 1_000_000_007] {
+            let mut expected = 3;
+            for _ in 0..cycle.equivalent_step(steps) {
+                expected = pseudo_random(&expected);
+            }
+            assert_eq!(state_after(3, pseudo_random, steps), expected);
+        }
+    }
+}
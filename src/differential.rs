@@ -0,0 +1,434 @@
+/// Differential testing of alternative implementations, e.g. a brute force and an optimized solution.
+///
+/// A [`Differential`] generates random inputs of growing size, runs every [`Variant`] on them and
+/// compares the results to the first variant. If they disagree, the input is shrunk to a minimal
+/// counterexample before the test fails.
+///
+/// ```
+/// # use advent_of_code::differential::Differential;
+/// # use advent_of_code::variants;
+/// Differential::new(|rng, size| rng.below(size as u64 * 100))
+///     .check(&variants![
+///         loop_sum => |n: &u64| (1..=*n).sum::<u64>(),
+///         gauss => |n: &u64| n * (n + 1) / 2,
+///     ]);
+/// ```
+use std::env;
+use std::fmt::Debug;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable that sets the seed of all differential tests, e.g. to reproduce a failure.
+/// `AOC_SEED=random` picks a new seed on every run to explore more inputs.
+pub const SEED_VAR: &str = "AOC_SEED";
+
+/// Seed of differential tests without [`Differential::seed`] or [`SEED_VAR`], so that `cargo test`
+/// checks the same inputs on every run.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Upper bound for the number of accepted shrink steps, in case `shrink` never runs out of candidates.
+const MAX_SHRINK_STEPS: usize = 1000;
+
+/// A small, seedable pseudo random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. Returns `0` if `n` is `0`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            self.next_u64() % n
+        }
+    }
+
+    /// A number in `min..=max`.
+    pub fn between(&mut self, min: u64, max: u64) -> u64 {
+        min + self.below(max - min + 1)
+    }
+
+    /// `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+}
+
+/// Values that can be reduced to simpler values of the same type.
+pub trait Shrink: Sized {
+    /// Simpler candidates for `self`, the most aggressive reduction first.
+    /// Returns an empty list if `self` can't be simplified any further.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self / 2, self.saturating_sub(1)];
+                candidates.dedup();
+                candidates.retain(|candidate| candidate < self);
+                candidates
+            }
+        }
+    )*};
+}
+
+macro_rules! shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![0, self.saturating_abs(), self / 2, self - self.signum()];
+                candidates.dedup();
+                candidates.retain(|candidate| {
+                    candidate.unsigned_abs() < self.unsigned_abs()
+                        || (*self < 0 && self.checked_neg() == Some(*candidate))
+                });
+                candidates
+            }
+        }
+    )*};
+}
+
+shrink_unsigned!(u8, u16, u32, u64, u128, usize);
+shrink_signed!(i8, i16, i32, i64, i128, isize);
+
+impl Shrink for bool {
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Removes the first and second half, then single elements, then shrinks single elements.
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            candidates.push(self[half..].to_vec());
+            candidates.push(self[..half].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut candidate = self.clone();
+            candidate.remove(i);
+            candidates.push(candidate);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for shrunk in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = shrunk;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Shrink + Clone, const N: usize> Shrink for [T; N] {
+    fn shrink(&self) -> Vec<Self> {
+        self.iter()
+            .enumerate()
+            .flat_map(|(i, item)| {
+                item.shrink().into_iter().map(move |shrunk| {
+                    let mut candidate = self.clone();
+                    candidate[i] = shrunk;
+                    candidate
+                })
+            })
+            .collect()
+    }
+}
+
+macro_rules! shrink_tuple {
+    ($($name:ident : $index:tt),+) => {
+        impl<$($name: Shrink + Clone),+> Shrink for ($($name,)+) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                $(
+                    for shrunk in self.$index.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$index = shrunk;
+                        candidates.push(candidate);
+                    }
+                )+
+                candidates
+            }
+        }
+    };
+}
+
+shrink_tuple!(A: 0);
+shrink_tuple!(A: 0, B: 1);
+shrink_tuple!(A: 0, B: 1, C: 2);
+shrink_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+/// A named implementation that maps an input to a result.
+pub struct Variant<I: ?Sized, O> {
+    pub name: &'static str,
+    pub run: fn(&I) -> O,
+}
+
+/// Declares the implementation variants to compare, the reference implementation first.
+///
+/// ```
+/// # use advent_of_code::variants;
+/// let variants = variants![
+///     recursive => |n: &u64| (1..=*n).product::<u64>(),
+///     iterative => |n: &u64| (1..=*n).fold(1, |acc, x| acc * x),
+/// ];
+/// assert_eq!(variants[1].name, "iterative");
+/// ```
+#[macro_export]
+macro_rules! variants {
+    ($($name:ident => $run:expr),+ $(,)?) => {
+        [$($crate::differential::Variant { name: stringify!($name), run: $run }),+]
+    };
+}
+
+/// An input on which variants disagree, together with the result of every variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<I, O> {
+    /// The shrunk input.
+    pub input: I,
+    /// The input as generated, before shrinking.
+    pub original: I,
+    pub results: Vec<(&'static str, O)>,
+    pub seed: u64,
+    /// Number of accepted shrink steps from `original` to `input`.
+    pub shrink_steps: usize,
+}
+
+/// A differential test. Configure it with the builder methods, then call [`Differential::check`].
+pub struct Differential<I> {
+    generate: fn(&mut Rng, usize) -> I,
+    assume: fn(&I) -> bool,
+    cases: usize,
+    max_size: usize,
+    seed: Option<u64>,
+}
+
+impl<I: Clone + Debug + Shrink> Differential<I> {
+    /// Creates a test that generates its inputs with `generate(rng, size)`.
+    /// `size` grows from `0` to the configured [`Differential::max_size`] over all cases.
+    #[must_use]
+    pub fn new(generate: fn(&mut Rng, usize) -> I) -> Self {
+        Differential {
+            generate,
+            assume: |_| true,
+            cases: 100,
+            max_size: 20,
+            seed: None,
+        }
+    }
+
+    /// Number of generated inputs, `100` by default.
+    #[must_use]
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Upper bound for the size passed to the generator, `20` by default.
+    #[must_use]
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Fixes the seed. By default, the seed is read from `AOC_SEED` or [`DEFAULT_SEED`] is used.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Restricts the inputs to those the variants are expected to agree on.
+    /// Applies to generated inputs as well as to shrink candidates.
+    #[must_use]
+    pub fn assume(mut self, assume: fn(&I) -> bool) -> Self {
+        self.assume = assume;
+        self
+    }
+
+    fn resolve_seed(&self) -> u64 {
+        if let Some(seed) = self.seed {
+            return seed;
+        }
+        match env::var(SEED_VAR).as_deref() {
+            Ok("random") => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_nanos() as u64)
+                .unwrap_or_default(),
+            Ok(seed) => seed.parse().unwrap_or_else(|_| {
+                panic!("{SEED_VAR} must be a number or `random`, got `{seed}`")
+            }),
+            Err(_) => DEFAULT_SEED,
+        }
+    }
+
+    /// Runs all cases and returns the first mismatch, shrunk to a minimal input.
+    pub fn find_mismatch<O: PartialEq>(
+        &self,
+        variants: &[Variant<I, O>],
+    ) -> Option<Mismatch<I, O>> {
+        let seed = self.resolve_seed();
+        let mut rng = Rng::new(seed);
+
+        let original = (0..self.cases)
+            .map(|case| (self.generate)(&mut rng, self.max_size * case / self.cases.max(1)))
+            .filter(|input| (self.assume)(input))
+            .find(|input| disagree(variants, input).is_some())?;
+
+        let mut input = original.clone();
+        let mut results = disagree(variants, &input)?;
+        let mut shrink_steps = 0;
+
+        'shrink: while shrink_steps < MAX_SHRINK_STEPS {
+            for candidate in input.shrink() {
+                if !(self.assume)(&candidate) {
+                    continue;
+                }
+                if let Some(candidate_results) = disagree(variants, &candidate) {
+                    input = candidate;
+                    results = candidate_results;
+                    shrink_steps += 1;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+
+        Some(Mismatch {
+            input,
+            original,
+            results,
+            seed,
+            shrink_steps,
+        })
+    }
+
+    /// Runs all cases.
+    ///
+    /// # Panics
+    ///
+    /// If a variant disagrees with the first one, listing the shrunk input and the result of every variant.
+    pub fn check<O: PartialEq + Debug>(&self, variants: &[Variant<I, O>]) {
+        if let Some(mismatch) = self.find_mismatch(variants) {
+            let results: Vec<String> = mismatch
+                .results
+                .iter()
+                .map(|(name, result)| format!("  {name}: {result:?}"))
+                .collect();
+
+            panic!(
+                "variants disagree on input {:?}\n{}\n(shrunk from {:?} in {} steps, rerun with {SEED_VAR}={})",
+                mismatch.input,
+                results.join("\n"),
+                mismatch.original,
+                mismatch.shrink_steps,
+                mismatch.seed
+            );
+        }
+    }
+}
+
+/// The results of all variants, if any of them differs from the first one.
+fn disagree<I, O: PartialEq>(
+    variants: &[Variant<I, O>],
+    input: &I,
+) -> Option<Vec<(&'static str, O)>> {
+    let results: Vec<(&'static str, O)> = variants
+        .iter()
+        .map(|variant| (variant.name, (variant.run)(input)))
+        .collect();
+
+    results
+        .iter()
+        .any(|(_, result)| result != &results[0].1)
+        .then_some(results)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Differential, Rng, Shrink};
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            let n = a.between(3, 9);
+            assert_eq!(n, b.between(3, 9));
+            assert_eq!((3..=9).contains(&n), true);
+        }
+    }
+
+    #[test]
+    fn shrinks_towards_zero() {
+        assert_eq!(10_u32.shrink(), vec![0, 5, 9]);
+        assert_eq!(1_u32.shrink(), vec![0]);
+        assert_eq!(0_u32.shrink(), Vec::<u32>::new());
+        assert_eq!((-4_i32).shrink(), vec![0, 4, -2, -3]);
+        assert_eq!(vec![3_u8].shrink(), vec![vec![], vec![0], vec![1], vec![2]]);
+        assert_eq!(
+            (2_u8, true).shrink(),
+            vec![(0, true), (1, true), (2, false)]
+        );
+    }
+
+    #[test]
+    fn accepts_agreeing_variants() {
+        let mismatch = Differential::new(|rng, size| rng.below(size as u64 * 10))
+            .seed(1)
+            .find_mismatch(&variants![
+                doubled => |n: &u64| n * 2,
+                added => |n: &u64| n + n,
+            ]);
+        assert_eq!(mismatch, None);
+    }
+
+    #[test]
+    fn shrinks_mismatches() {
+        let mismatch = Differential::new(|rng, size| vec![rng.below(size as u64 * 10); size])
+            .seed(1)
+            .find_mismatch(&variants![
+                sum => |v: &Vec<u64>| v.iter().sum::<u64>(),
+                capped => |v: &Vec<u64>| v.iter().sum::<u64>().min(40),
+            ])
+            .unwrap();
+
+        assert_eq!(mismatch.input.iter().sum::<u64>(), 41);
+        assert_eq!(mismatch.results, vec![("sum", 41), ("capped", 40)]);
+        assert_eq!(mismatch.original.len() >= mismatch.input.len(), true);
+    }
+
+    #[test]
+    fn skips_inputs_outside_assumptions() {
+        let mismatch = Differential::new(|rng, size| rng.below(size as u64 * 10))
+            .seed(1)
+            .assume(|n| n % 2 == 0)
+            .find_mismatch(&variants![
+                halved => |n: &u64| n / 2,
+                shifted => |n: &u64| (n + 1) >> 1,
+            ]);
+        assert_eq!(mismatch, None);
+    }
+}
@@ -0,0 +1,426 @@
+/// Detectors for "find the picture" puzzles, where the points of a simulation form an image at one step.
+///
+/// Every [`Detector`] scores an [`Occupancy`] grid so that structured frames score higher than noise,
+/// without knowing what the image looks like or how large it is. A [`Search`] scans the steps of a
+/// simulation and ranks them by how far their score stands out from the average step.
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+/// The occupied cells of a grid at one step of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occupancy {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    /// The occupied cells as `(x, y)`, each listed once.
+    points: Vec<(usize, usize)>,
+}
+
+impl Occupancy {
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![false; width * height],
+            points: vec![],
+        }
+    }
+
+    /// A grid with the given cells occupied. Cells listed more than once count once.
+    ///
+    /// # Panics
+    ///
+    /// If a point lies outside of the grid.
+    #[must_use]
+    pub fn from_points(
+        width: usize,
+        height: usize,
+        points: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        let mut occupancy = Self::new(width, height);
+        points.into_iter().for_each(|(x, y)| {
+            occupancy.insert(x, y);
+        });
+        occupancy
+    }
+
+    /// Occupies a cell. Returns `false` if it was occupied already.
+    ///
+    /// # Panics
+    ///
+    /// If the cell lies outside of the grid.
+    pub fn insert(&mut self, x: usize, y: usize) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+        let cell = &mut self.cells[y * self.width + x];
+        if *cell {
+            return false;
+        }
+        *cell = true;
+        self.points.push((x, y));
+        true
+    }
+
+    #[must_use]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[y * self.width + x]
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn points(&self) -> &[(usize, usize)] {
+        &self.points
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl Display for Occupancy {
+    /// Renders occupied cells as `██`, so that the frame keeps its aspect ratio in a terminal.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let row: String = row
+                .iter()
+                .map(|cell| if *cell { "██" } else { "  " })
+                .collect();
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// Measures how structured a frame is. Higher scores mean more structure for every detector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Detector {
+    /// Negative Shannon entropy of the 2x2 blocks of the grid, in bits. Images repeat few block patterns.
+    Entropy,
+    /// Cells per run of equal cells in each row, i.e. the compression ratio of a run-length encoding.
+    Compression,
+    /// Negative variance of the positions of the occupied cells. Images cluster, noise spreads out.
+    Variance,
+    /// Number of cells in the largest group of horizontally or vertically adjacent occupied cells.
+    LargestComponent,
+    /// Length of the longest horizontal or vertical line of occupied cells.
+    LongestRun,
+}
+
+impl Detector {
+    pub const ALL: [Detector; 5] = [
+        Detector::Entropy,
+        Detector::Compression,
+        Detector::Variance,
+        Detector::LargestComponent,
+        Detector::LongestRun,
+    ];
+
+    #[must_use]
+    pub fn score(&self, frame: &Occupancy) -> f64 {
+        match self {
+            Detector::Entropy => -block_entropy(frame),
+            Detector::Compression => compression_ratio(frame),
+            Detector::Variance => -position_variance(frame),
+            Detector::LargestComponent => largest_component(frame) as f64,
+            Detector::LongestRun => longest_run(frame) as f64,
+        }
+    }
+}
+
+fn block_entropy(frame: &Occupancy) -> f64 {
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for y in (0..frame.height).step_by(2) {
+        for x in (0..frame.width).step_by(2) {
+            let block = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                .iter()
+                .enumerate()
+                .filter(|(_, (dx, dy))| frame.contains(x + dx, y + dy))
+                .fold(0, |block, (bit, _)| block | (1 << bit));
+            *counts.entry(block).or_default() += 1;
+        }
+    }
+
+    let total: usize = counts.values().sum();
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+fn compression_ratio(frame: &Occupancy) -> f64 {
+    let runs: usize = frame
+        .rows()
+        .map(|row| 1 + row.windows(2).filter(|pair| pair[0] != pair[1]).count())
+        .sum();
+    frame.cells.len() as f64 / runs as f64
+}
+
+fn position_variance(frame: &Occupancy) -> f64 {
+    let n = frame.points.len() as f64;
+    if n == 0.0 {
+        return 0.0;
+    }
+
+    let variance = |coordinate: fn(&(usize, usize)) -> usize| {
+        let mean = frame
+            .points
+            .iter()
+            .map(|p| coordinate(p) as f64)
+            .sum::<f64>()
+            / n;
+        frame
+            .points
+            .iter()
+            .map(|p| (coordinate(p) as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n
+    };
+
+    variance(|(x, _)| *x) + variance(|(_, y)| *y)
+}
+
+fn largest_component(frame: &Occupancy) -> usize {
+    let mut visited = vec![false; frame.cells.len()];
+    let mut largest = 0;
+
+    for &(x, y) in &frame.points {
+        if visited[y * frame.width + x] {
+            continue;
+        }
+        visited[y * frame.width + x] = true;
+
+        let mut size = 0;
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            size += 1;
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if frame.contains(nx, ny) && !visited[ny * frame.width + nx] {
+                    visited[ny * frame.width + nx] = true;
+                    stack.push((nx, ny));
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+
+    largest
+}
+
+fn longest_run(frame: &Occupancy) -> usize {
+    let longest_in = |cells: &mut dyn Iterator<Item = bool>| {
+        cells
+            .fold((0, 0), |(longest, current), occupied| {
+                let current = if occupied { current + 1 } else { 0 };
+                (longest.max(current), current)
+            })
+            .0
+    };
+
+    let horizontal = (0..frame.height)
+        .map(|y| longest_in(&mut (0..frame.width).map(|x| frame.contains(x, y))))
+        .max()
+        .unwrap_or_default();
+    let vertical = (0..frame.width)
+        .map(|x| longest_in(&mut (0..frame.height).map(|y| frame.contains(x, y))))
+        .max()
+        .unwrap_or_default();
+
+    horizontal.max(vertical)
+}
+
+/// A step that stands out, with the frame at that step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub step: usize,
+    pub score: f64,
+    /// Standard deviations between the score of this step and the average score of all scanned steps.
+    pub z_score: f64,
+    pub frame: Occupancy,
+}
+
+impl Display for Candidate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "step {}: score {:.2}, {:.1}σ above average",
+            self.step, self.score, self.z_score
+        )?;
+        write!(f, "{}", self.frame)
+    }
+}
+
+/// Scans the steps of a simulation and ranks them by the score of a [`Detector`].
+///
+/// ```
+/// # use advent_of_code::emergence::{Detector, Occupancy, Search};
+/// // a line of 5 points that only forms at step 3
+/// let candidates = Search::new(Detector::LongestRun, 0..10).run(|step| {
+///     Occupancy::from_points(10, 10, (0..5).map(|i| if step == 3 { (i, 0) } else { (2 * i, i) }))
+/// });
+/// assert_eq!(candidates[0].step, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Search {
+    detector: Detector,
+    steps: Range<usize>,
+    top: usize,
+}
+
+impl Search {
+    #[must_use]
+    pub fn new(detector: Detector, steps: Range<usize>) -> Self {
+        Self {
+            detector,
+            steps,
+            top: 1,
+        }
+    }
+
+    /// Number of candidates to return, e.g. to show the best frames for confirmation. Defaults to 1.
+    #[must_use]
+    pub fn top(mut self, top: usize) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// Scores the frame of every step, calling `frame_at` with the steps in ascending order so that
+    /// simulations can advance one step per call. Returns the best candidates, best first. Ties go
+    /// to the earlier step.
+    pub fn run(self, mut frame_at: impl FnMut(usize) -> Occupancy) -> Vec<Candidate> {
+        let mut scores = vec![];
+        let mut best: Vec<Candidate> = vec![];
+
+        for step in self.steps {
+            let frame = frame_at(step);
+            let score = self.detector.score(&frame);
+            scores.push(score);
+
+            if best.len() < self.top || best.last().is_some_and(|worst| score > worst.score) {
+                let index = best.partition_point(|candidate| candidate.score >= score);
+                best.insert(
+                    index,
+                    Candidate {
+                        step,
+                        score,
+                        z_score: 0.0,
+                        frame,
+                    },
+                );
+                best.truncate(self.top);
+            }
+        }
+
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let deviation = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n).sqrt();
+        for candidate in &mut best {
+            if deviation > 0.0 {
+                candidate.z_score = (candidate.score - mean) / deviation;
+            }
+        }
+
+        best
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Detector, Occupancy, Search};
+    use crate::differential::Rng;
+
+    /// A filled triangle of `size` rows, drawn at `(left, top)`.
+    fn triangle(size: usize, left: usize, top: usize) -> Vec<(usize, usize)> {
+        (0..size)
+            .flat_map(|row| (size - 1 - row..size + row).map(move |x| (left + x, top + row)))
+            .collect()
+    }
+
+    fn noise(rng: &mut Rng, count: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+        (0..count)
+            .map(|_| {
+                (
+                    rng.below(width as u64) as usize,
+                    rng.below(height as u64) as usize,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scores_pictures_above_noise() {
+        let mut rng = Rng::new(14);
+        let picture = triangle(10, 20, 20);
+        let picture_frame = Occupancy::from_points(60, 60, picture.clone());
+        let noise_frame = Occupancy::from_points(60, 60, noise(&mut rng, picture.len(), 60, 60));
+
+        for detector in Detector::ALL {
+            assert_eq!(
+                detector.score(&picture_frame) > detector.score(&noise_frame),
+                true,
+                "{detector:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn measures_components_and_runs() {
+        let frame = Occupancy::from_points(5, 3, [(0, 0), (1, 0), (2, 0), (2, 1), (4, 2), (4, 2)]);
+        assert_eq!(frame.points().len(), 5);
+        assert_eq!(Detector::LargestComponent.score(&frame), 4.0);
+        assert_eq!(Detector::LongestRun.score(&frame), 3.0);
+        assert_eq!(Detector::Compression.score(&frame), 15.0 / 7.0);
+    }
+
+    #[test]
+    fn finds_emerging_pictures() {
+        let picture = triangle(8, 30, 10);
+        for detector in Detector::ALL {
+            let mut rng = Rng::new(7);
+            let candidates = Search::new(detector, 0..50).top(3).run(|step| {
+                let points = if step == 37 {
+                    picture.clone()
+                } else {
+                    noise(&mut rng, picture.len(), 70, 40)
+                };
+                Occupancy::from_points(70, 40, points)
+            });
+
+            assert_eq!(candidates.len(), 3);
+            assert_eq!(candidates[0].step, 37, "{detector:?}");
+            assert_eq!(candidates[0].z_score > 3.0, true, "{detector:?}");
+            assert_eq!(candidates[1].score >= candidates[2].score, true);
+        }
+    }
+
+    #[test]
+    fn renders_frames() {
+        let frame = Occupancy::from_points(3, 2, [(0, 0), (2, 1)]);
+        assert_eq!(frame.to_string(), "██\n    ██\n");
+    }
+}
@@ -1,3 +1,9 @@
+pub mod cycle;
+pub mod differential;
+pub mod emergence;
+pub mod math;
 pub mod template;
+#[cfg(feature = "visualize")]
+pub mod visualize;
 
 // Use this file to add helper functions and additional modules.
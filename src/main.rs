@@ -1,14 +1,19 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    add_example, all, download, fuzz, progress, read, scaffold, solve, stress, time,
+};
+use advent_of_code::template::{cli, Error};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
 
 mod args {
+    use advent_of_code::template::cli::{self, Command, Shell};
+    use advent_of_code::template::params::{self, Override};
     use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{fuzz, stress};
+    use std::path::PathBuf;
 
     pub enum AppArguments {
         Download {
@@ -21,124 +26,367 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            examples: u8,
+        },
+        AddExample {
+            day: Day,
+            part: u8,
+            expected: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            variant: Option<String>,
+            params: Vec<Override>,
+            verbosity: u8,
         },
         All {
             release: bool,
+            verbosity: u8,
+            log_days: Vec<Day>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            memory: bool,
+            variants: bool,
+            save_baseline: Option<String>,
+            baseline: Option<String>,
+        },
+        Stress {
+            day: Day,
+            sizes: Vec<usize>,
+            seed: u64,
+        },
+        Fuzz {
+            day: Day,
+            iterations: usize,
+            seed: u64,
+            release: bool,
+        },
+        Export {
+            format: String,
+            out: Option<PathBuf>,
+        },
+        Progress {
+            store: bool,
         },
         #[cfg(feature = "today")]
         Today,
+        Completions {
+            shell: Shell,
+        },
+        Help {
+            command: Option<&'static Command>,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<AppArguments, String> {
+        let raw: Vec<String> = std::env::args_os()
+            .skip(1)
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let name = match args.subcommand().map_err(|e| e.to_string())? {
+            Some(name) => name,
+            None if args.contains(["-h", "--help"]) => {
+                return Ok(AppArguments::Help { command: None })
+            }
+            None => return Err(format!("no command specified.\n\n{}", cli::help())),
+        };
+
+        let Some(command) = cli::find(&name) else {
+            return Err(format!(
+                "unknown command `{name}`.{}\n\n{}",
+                command_hint(&name),
+                cli::help()
+            ));
+        };
+
+        if args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help {
+                command: Some(command),
+            });
+        }
+
+        if let Some(err) = cli::check_flags(command, &raw) {
+            return Err(with_usage(command, &err));
+        }
+
+        let app_args = parse_command(command, &mut args)
+            .map_err(|e| with_usage(command, &describe_error(command, e)))?;
+
+        let remaining = args.finish();
+        if let Some(arg) = remaining.first() {
+            let message = format!("unexpected argument `{}`.", arg.to_string_lossy());
+            return Err(with_usage(command, &message));
+        }
+
+        Ok(app_args)
+    }
+
+    fn parse_command(
+        command: &Command,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, pico_args::Error> {
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                verbosity: parse_verbosity(args),
+                log_days: args.values_from_str("--log-day")?,
             },
-            Some("time") => {
+            "time" => {
+                if let Some(format) = args.opt_value_from_str("--export")? {
+                    return Ok(AppArguments::Export {
+                        format,
+                        out: args.opt_value_from_str("--out")?,
+                    });
+                }
+
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let variants = args.contains("--variants");
+                let save_baseline = args.opt_value_from_str("--save-baseline")?;
+                let baseline = args.opt_value_from_str("--baseline")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    memory,
+                    variants,
+                    save_baseline,
+                    baseline,
                 }
             }
-            Some("download") => AppArguments::Download {
+            "stress" => AppArguments::Stress {
+                day: args.free_from_str()?,
+                sizes: args
+                    .opt_value_from_fn("--sizes", stress::parse_sizes)?
+                    .unwrap_or_else(|| vec![10, 100, 1000]),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(0),
+            },
+            "fuzz" => AppArguments::Fuzz {
+                day: args.free_from_str()?,
+                iterations: args.opt_value_from_str("--iterations")?.unwrap_or(10000),
+                seed: args
+                    .opt_value_from_str("--seed")?
+                    .unwrap_or_else(fuzz::random_seed),
+                release: args.contains("--release"),
+            },
+            "progress" => AppArguments::Progress {
+                store: args.contains("--store"),
+            },
+            "download" => AppArguments::Download {
                 day: args.free_from_str()?,
             },
-            Some("read") => AppArguments::Read {
+            "read" => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            "scaffold" => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                examples: args
+                    .opt_value_from_fn("--examples", parse_example_count)?
+                    .unwrap_or(1),
+            },
+            "add-example" => AppArguments::AddExample {
+                day: args.free_from_str()?,
+                part: args.opt_value_from_fn("--part", parse_part)?.unwrap_or(1),
+                expected: args.opt_value_from_str("--expect")?,
             },
-            Some("solve") => AppArguments::Solve {
+            "solve" => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
+                submit: args.opt_value_from_fn("--submit", parse_part)?,
                 dhat: args.contains("--dhat"),
+                variant: args.opt_value_from_str("--variant")?,
+                params: args.values_from_fn("--param", params::parse_override)?,
+                verbosity: parse_verbosity(args),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            "today" => AppArguments::Today,
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            "help" => AppArguments::Help {
+                command: args.opt_free_from_fn(|name| {
+                    cli::find(name).ok_or(format!("unknown command{}", command_hint(name)))
+                })?,
+            },
+            name => unreachable!("command `{name}` is described but not parsed"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        Ok(app_args)
+    }
+
+    fn parse_part(s: &str) -> Result<u8, &'static str> {
+        match s {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err("expecting part 1 or 2"),
         }
+    }
 
-        Ok(app_args)
+    fn parse_example_count(s: &str) -> Result<u8, &'static str> {
+        match s.parse() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err("expecting a number of examples between 1 and 255"),
+        }
+    }
+
+    fn command_hint(name: &str) -> String {
+        match cli::suggest(name, cli::commands().iter().map(|c| c.name)) {
+            Some(command) => format!(", did you mean `{command}`?"),
+            None => ".".into(),
+        }
+    }
+
+    fn describe_error(command: &Command, err: pico_args::Error) -> String {
+        match err {
+            pico_args::Error::MissingArgument => match &command.positional {
+                Some(positional) => format!("missing argument <{}>.", positional.value.name),
+                None => err.to_string(),
+            },
+            pico_args::Error::OptionWithoutAValue(flag) => {
+                format!("option `{flag}` requires a value.")
+            }
+            pico_args::Error::Utf8ArgumentParsingFailed { value, cause } => {
+                let cause = cause.trim_end_matches('.');
+                if cause.ends_with('?') {
+                    format!("invalid value `{value}`: {cause}")
+                } else {
+                    format!("invalid value `{value}`: {cause}.")
+                }
+            }
+            err => format!("{err}."),
+        }
+    }
+
+    fn with_usage(command: &Command, message: &str) -> String {
+        format!("{message}\n\n{}", cli::usage(command))
+    }
+
+    /// Counts `--verbose` / `-v` flags, e.g. `-v -v` enables trace messages.
+    fn parse_verbosity(args: &mut pico_args::Arguments) -> u8 {
+        let mut verbosity = 0;
+        while args.contains(["-v", "--verbose"]) {
+            verbosity += 1;
+        }
+        verbosity
     }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    if let Err(err) = parse().map_err(Error::Usage).and_then(run) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            release,
+            verbosity,
+            log_days,
+        } => all::handle(release, verbosity, &log_days),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            memory,
+            variants,
+            save_baseline,
+            baseline,
+        } => {
+            if variants {
+                time::compare_variants(day)
+            } else {
+                time::handle(
+                    day,
+                    all,
+                    store,
+                    memory,
+                    save_baseline.as_deref(),
+                    baseline.as_deref(),
+                )
+            }
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        AppArguments::Export { format, out } => time::export(&format, out.as_deref()),
+        AppArguments::Stress { day, sizes, seed } => stress::handle(day, sizes, seed),
+        AppArguments::Fuzz {
+            day,
+            iterations,
+            seed,
+            release,
+        } => fuzz::handle(day, iterations, seed, release),
+        AppArguments::Progress { store } => progress::handle(store),
+        AppArguments::Completions { shell } => {
+            print!("{}", cli::completions(shell));
+            Ok(())
+        }
+        AppArguments::Help { command } => {
+            match command {
+                Some(command) => print!("{}", cli::usage(command)),
+                None => print!("{}", cli::help()),
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            Ok(())
+        }
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::AddExample {
+            day,
+            part,
+            expected,
+        } => add_example::handle(day, part, expected.as_deref()),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            examples,
+        } => {
+            scaffold::handle(day, overwrite, examples)?;
+            if download {
+                download::handle(day)?;
             }
+            Ok(())
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            variant,
+            params,
+            verbosity,
+        } => solve::handle(
+            day,
+            release,
+            dhat,
+            submit,
+            variant.as_deref(),
+            &params,
+            verbosity,
+        ),
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Day::today() {
+            Some(day) => {
+                scaffold::handle(day, false, 1)?;
+                download::handle(day)?;
+                read::handle(day)
+            }
+            None => Err(Error::Other(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
-    };
+    }
 }
@@ -0,0 +1,433 @@
+/// Number-theory and small linear-algebra helpers shared between solutions.
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+/// Greatest common divisor of `a` and `b`. `gcd(0, 0)` is `0`.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of `a` and `b`. `lcm(0, x)` is `0`.
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// Extended euclidean algorithm.
+///
+/// Returns `(g, x, y)` such that `a * x + b * y == g` where `g` is the non-negative gcd of `a` and `b`.
+#[must_use]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_i128(i128::from(a), i128::from(b));
+    // NOTE: the coefficients are bounded by |a| and |b|, so they always fit back into an i64.
+    #[allow(clippy::cast_possible_truncation)]
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Multiplicative inverse of `a` modulo `m`, in range `0..m`.
+/// Returns [`None`] if `a` and `m` are not coprime or `m` is not positive.
+#[must_use]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// Chinese remainder theorem for a system of congruences `x ≡ residue (mod modulus)`.
+///
+/// The moduli do not need to be pairwise coprime. Returns the smallest non-negative solution
+/// together with the combined modulus (the lcm of all moduli), or [`None`] if the system has no
+/// solution, a modulus is not positive or the combined modulus overflows.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// ```
+#[must_use]
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut residue: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let m = i128::from(m);
+        let r = i128::from(r).rem_euclid(m);
+
+        let (g, p, _) = extended_gcd_i128(modulus, m);
+        if (r - residue) % g != 0 {
+            return None;
+        }
+
+        let lcm = modulus / g * m;
+        let step = ((r - residue) / g * p).rem_euclid(m / g);
+        residue = (residue + modulus * step).rem_euclid(lcm);
+        modulus = lcm;
+
+        if modulus > i128::from(i64::MAX) {
+            return None;
+        }
+    }
+
+    // NOTE: both values are bounded by the combined modulus which was checked above.
+    #[allow(clippy::cast_possible_truncation)]
+    Some((residue as i64, modulus as i64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of decimal digits of `n`. `0` has one digit.
+#[must_use]
+pub fn num_digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Splits a number with an even amount of digits into its left and right half,
+/// e.g. `1234` into `(12, 34)` and `1000` into `(10, 0)`.
+/// Returns [`None`] for numbers with an odd amount of digits.
+#[must_use]
+pub fn split_digits(n: u64) -> Option<(u64, u64)> {
+    let digits = num_digits(n);
+    if digits % 2 == 1 {
+        return None;
+    }
+    let divisor = 10_u64.pow(digits / 2);
+    Some((n / divisor, n % divisor))
+}
+
+/// Concatenates the decimal digits of two numbers, e.g. `12` and `345` into `12345`.
+///
+/// # Panics
+/// Panics in debug builds if the result overflows.
+#[must_use]
+pub fn concat_digits(left: u64, right: u64) -> u64 {
+    left * 10_u64.pow(num_digits(right)) + right
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numer: i128,
+    denom: i128,
+}
+
+impl Ratio {
+    /// Creates a new [`Ratio`].
+    ///
+    /// # Panics
+    /// Panics if `denom` is zero.
+    #[must_use]
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "denominator of a ratio must not be zero");
+        let sign = if denom < 0 { -1 } else { 1 };
+        let (g, _, _) = extended_gcd_i128(numer, denom);
+        let g = if g == 0 { 1 } else { g };
+        Self {
+            numer: sign * numer / g,
+            denom: sign * denom / g,
+        }
+    }
+
+    /// Creates a [`Ratio`] that represents an integer.
+    #[must_use]
+    pub fn from_integer(value: i128) -> Self {
+        Self {
+            numer: value,
+            denom: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    #[must_use]
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    #[must_use]
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// Returns the integer value if the ratio represents a whole number.
+    #[must_use]
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // NOTE: denominators are always positive, so cross-multiplying keeps the order.
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The solution set of a linear system of two equations in two unknowns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearSolution {
+    /// The system has exactly one (rational) solution `(x, y)`.
+    Unique(Ratio, Ratio),
+    /// Both equations describe the same line, e.g. because the coefficient vectors are collinear.
+    Infinite,
+    /// The equations contradict each other.
+    Inconsistent,
+}
+
+/// Solves the system `a1 * x + b1 * y = c1`, `a2 * x + b2 * y = c2` exactly,
+/// with each equation given as `[a, b, c]`.
+///
+/// ```
+/// # use advent_of_code::math::{solve_linear_2x2, LinearSolution, Ratio};
+/// let solution = solve_linear_2x2([94, 22, 8400], [34, 67, 5400]);
+/// assert_eq!(solution, LinearSolution::Unique(Ratio::from_integer(80), Ratio::from_integer(40)));
+/// ```
+#[must_use]
+pub fn solve_linear_2x2(first: [i64; 3], second: [i64; 3]) -> LinearSolution {
+    let [a1, b1, c1] = first.map(i128::from);
+    let [a2, b2, c2] = second.map(i128::from);
+
+    let determinant = a1 * b2 - a2 * b1;
+    if determinant != 0 {
+        return LinearSolution::Unique(
+            Ratio::new(c1 * b2 - c2 * b1, determinant),
+            Ratio::new(a1 * c2 - a2 * c1, determinant),
+        );
+    }
+
+    // the coefficient rows are collinear: the system is solvable iff the augmented rows are as well.
+    let is_consistent = a1 * c2 == a2 * c1 && b1 * c2 == b2 * c1;
+    let first_is_trivial = a1 == 0 && b1 == 0;
+    let second_is_trivial = a2 == 0 && b2 == 0;
+
+    if (first_is_trivial && c1 != 0) || (second_is_trivial && c2 != 0) || !is_consistent {
+        LinearSolution::Inconsistent
+    } else {
+        LinearSolution::Infinite
+    }
+}
+
+/// Finds the non-negative integer solution `(x, y)` of `a * x + b * y = c` that minimizes
+/// `cost_x * x + cost_y * y`, e.g. to pick the cheapest combination of two moves.
+///
+/// `a`, `b`, `cost_x` and `cost_y` must be non-negative. Returns [`None`] if no such solution exists.
+///
+/// ```
+/// # use advent_of_code::math::min_cost_nonnegative_solution;
+/// // 3x + 2y = 12 has the solutions (0, 6), (2, 3) and (4, 0).
+/// assert_eq!(min_cost_nonnegative_solution(3, 2, 12, 3, 1), Some((0, 6)));
+/// assert_eq!(min_cost_nonnegative_solution(3, 2, 12, 1, 3), Some((4, 0)));
+/// ```
+#[must_use]
+pub fn min_cost_nonnegative_solution(
+    a: i64,
+    b: i64,
+    c: i64,
+    cost_x: i64,
+    cost_y: i64,
+) -> Option<(i64, i64)> {
+    debug_assert!(a >= 0 && b >= 0 && cost_x >= 0 && cost_y >= 0);
+
+    match (a, b) {
+        (0, 0) => return (c == 0).then_some((0, 0)),
+        (0, b) => return (c % b == 0 && c / b >= 0).then_some((0, c / b)),
+        (a, 0) => return (c % a == 0 && c / a >= 0).then_some((c / a, 0)),
+        _ => {}
+    }
+
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    let (g, p, q) = extended_gcd_i128(a, b);
+    if c % g != 0 {
+        return None;
+    }
+
+    // general solution: x = x0 + step_x * t, y = y0 - step_y * t
+    let (x0, y0) = (p * (c / g), q * (c / g));
+    let (step_x, step_y) = (b / g, a / g);
+
+    // x >= 0  <=>  t >= ceil(-x0 / step_x); y >= 0  <=>  t <= floor(y0 / step_y)
+    let t_min = div_ceil(-x0, step_x);
+    let t_max = y0.div_euclid(step_y);
+    if t_min > t_max {
+        return None;
+    }
+
+    // the cost is linear in t, so the optimum is at one end of the range.
+    let cost_slope = i128::from(cost_x) * step_x - i128::from(cost_y) * step_y;
+    let t = if cost_slope > 0 { t_min } else { t_max };
+
+    let x = i64::try_from(x0 + step_x * t).ok()?;
+    let y = i64::try_from(y0 - step_y * t).ok()?;
+    Some((x, y))
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -((-a).div_euclid(b))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(101, 103), 10403);
+        assert_eq!(lcm(0, 5), 0);
+    }
+
+    #[test]
+    fn computes_extended_gcd() {
+        for (a, b) in [(240, 46), (-240, 46), (7, 0), (0, -7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn computes_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(101, 103), Some(51));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(1, 0), None);
+    }
+
+    #[test]
+    fn solves_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 101), (-2, 103)]), Some((5251, 10403)));
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 0)]), None);
+    }
+
+    #[test]
+    fn handles_digits() {
+        assert_eq!(num_digits(0), 1);
+        assert_eq!(num_digits(9), 1);
+        assert_eq!(num_digits(10), 2);
+        assert_eq!(num_digits(u64::MAX), 20);
+        assert_eq!(split_digits(1234), Some((12, 34)));
+        assert_eq!(split_digits(1000), Some((10, 0)));
+        assert_eq!(split_digits(123), None);
+        assert_eq!(split_digits(0), None);
+        assert_eq!(concat_digits(12, 345), 12345);
+        assert_eq!(concat_digits(15, 0), 150);
+    }
+
+    #[test]
+    fn normalizes_ratios() {
+        assert_eq!(Ratio::new(4, -6), Ratio::new(-2, 3));
+        assert_eq!(Ratio::new(0, 5), Ratio::from_integer(0));
+        assert_eq!(Ratio::new(10, 5).to_integer(), Some(2));
+        assert_eq!(Ratio::new(1, 3).to_integer(), None);
+        assert!(Ratio::new(1, 3) < Ratio::new(1, 2));
+        assert_eq!(Ratio::new(-3, 6).to_string(), "-1/2");
+    }
+
+    #[test]
+    fn solves_unique_systems() {
+        assert_eq!(
+            solve_linear_2x2([94, 22, 8400], [34, 67, 5400]),
+            LinearSolution::Unique(Ratio::from_integer(80), Ratio::from_integer(40))
+        );
+        assert_eq!(
+            solve_linear_2x2([2, 0, 1], [0, 3, 1]),
+            LinearSolution::Unique(Ratio::new(1, 2), Ratio::new(1, 3))
+        );
+    }
+
+    #[test]
+    fn solves_degenerate_systems() {
+        assert_eq!(
+            solve_linear_2x2([4, 8, 40], [3, 6, 30]),
+            LinearSolution::Infinite
+        );
+        assert_eq!(
+            solve_linear_2x2([4, 3, 40], [8, 6, 30]),
+            LinearSolution::Inconsistent
+        );
+        assert_eq!(
+            solve_linear_2x2([0, 0, 0], [1, 1, 2]),
+            LinearSolution::Infinite
+        );
+        assert_eq!(
+            solve_linear_2x2([0, 0, 1], [1, 1, 2]),
+            LinearSolution::Inconsistent
+        );
+    }
+
+    #[test]
+    fn finds_cheapest_nonnegative_solution() {
+        assert_eq!(min_cost_nonnegative_solution(4, 8, 40, 3, 1), Some((0, 5)));
+        assert_eq!(min_cost_nonnegative_solution(6, 4, 24, 3, 1), Some((0, 6)));
+        assert_eq!(min_cost_nonnegative_solution(3, 2, 12, 1, 3), Some((4, 0)));
+        assert_eq!(min_cost_nonnegative_solution(4, 6, 7, 3, 1), None);
+        assert_eq!(min_cost_nonnegative_solution(5, 7, 3, 1, 1), None);
+        assert_eq!(min_cost_nonnegative_solution(0, 4, 8, 3, 1), Some((0, 2)));
+        assert_eq!(min_cost_nonnegative_solution(0, 0, 0, 3, 1), Some((0, 0)));
+    }
+}
@@ -0,0 +1,221 @@
+/// Module that compares benchmark timings against a named baseline.
+use crate::template::timings::{parse_duration_nanos, Timings, STEPS};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Changes below this percentage are treated as measurement noise if a run has no recorded spread,
+/// e.g. for baselines saved before spreads were recorded.
+const NOISE_THRESHOLD: f64 = 5.0;
+
+/// Changes below this difference are treated as measurement noise regardless of the spread.
+const MIN_DIFFERENCE_NANOS: f64 = 1_000.0;
+
+/// Change of a single step between a baseline and the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub step: &'static str,
+    pub baseline: String,
+    pub current: String,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    /// Standard deviation of the benchmark samples of the baseline, if it was recorded.
+    pub baseline_spread: Option<f64>,
+    /// Standard deviation of the benchmark samples of the current run, if it was recorded.
+    pub current_spread: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Significance {
+    Faster,
+    Slower,
+    Noise,
+}
+
+impl Delta {
+    /// Change relative to the baseline, negative when the current run is faster.
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_nanos == 0.0 {
+            0.0
+        } else {
+            (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+        }
+    }
+
+    /// A change is significant if the difference exceeds the combined spread of the samples of both
+    /// runs, or [`NOISE_THRESHOLD`] without spreads, and is at least [`MIN_DIFFERENCE_NANOS`].
+    pub fn significance(&self) -> Significance {
+        let spread = match (self.baseline_spread, self.current_spread) {
+            (Some(baseline), Some(current)) => baseline.hypot(current),
+            _ => self.baseline_nanos * NOISE_THRESHOLD / 100.0,
+        };
+        let difference = self.current_nanos - self.baseline_nanos;
+
+        if difference.abs() < spread.max(MIN_DIFFERENCE_NANOS) {
+            Significance::Noise
+        } else if difference < 0.0 {
+            Significance::Faster
+        } else {
+            Significance::Slower
+        }
+    }
+}
+
+/// Pairs up the steps measured in both `baseline` and `current`.
+/// Days or steps missing in either of them are skipped.
+#[must_use]
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(stored) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for step in STEPS {
+            let (Some(baseline), Some(current)) = (stored.step_time(step), timing.step_time(step))
+            else {
+                continue;
+            };
+            let (Some(baseline_nanos), Some(current_nanos)) = (
+                parse_duration_nanos(baseline),
+                parse_duration_nanos(current),
+            ) else {
+                continue;
+            };
+
+            deltas.push(Delta {
+                day: timing.day,
+                step,
+                baseline: baseline.into(),
+                current: current.into(),
+                baseline_nanos,
+                current_nanos,
+                baseline_spread: stored.spreads.get(step).copied(),
+                current_spread: timing.spreads.get(step).copied(),
+            });
+        }
+    }
+
+    deltas.sort_by_key(|delta| delta.day);
+    deltas
+}
+
+pub fn print_comparison(name: &str, deltas: &[Delta]) {
+    println!();
+    println!("Comparison with baseline `{name}`:");
+
+    if deltas.is_empty() {
+        println!("No steps in common with the baseline.");
+        return;
+    }
+
+    for delta in deltas {
+        let indicator = match delta.significance() {
+            Significance::Faster => format!("{ANSI_BOLD}faster{ANSI_RESET}"),
+            Significance::Slower => format!("{ANSI_BOLD}slower{ANSI_RESET}"),
+            Significance::Noise => "~ within noise".into(),
+        };
+
+        println!(
+            "Day {} {}: {} → {} ({:+.1}%) {indicator}",
+            delta.day,
+            delta.step,
+            delta.baseline,
+            delta.current,
+            delta.change_percent()
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{compare, Significance};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::Day,
+    };
+
+    fn timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            parse: None,
+            solve: None,
+            total_nanos: 0.0,
+            phases: vec![],
+            memory: vec![],
+            samples: BTreeMap::new(),
+            spreads: BTreeMap::new(),
+            answers: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn compares_common_steps() {
+        let baseline = Timings {
+            data: vec![
+                timing(day!(1), Some("10ms"), Some("2ms")),
+                timing(day!(2), Some("1ms"), None),
+            ],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("5ms"), Some("2.05ms")),
+                timing(day!(2), Some("1.5ms"), Some("1ms")),
+                timing(day!(3), Some("1ms"), Some("1ms")),
+            ],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].step, "part_1");
+        assert_eq!(deltas[0].change_percent(), -50.0);
+        assert_eq!(deltas[0].significance(), Significance::Faster);
+
+        assert_eq!(deltas[1].step, "part_2");
+        assert_eq!(deltas[1].significance(), Significance::Noise);
+
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].change_percent(), 50.0);
+        assert_eq!(deltas[2].significance(), Significance::Slower);
+    }
+
+    #[test]
+    fn ignores_changes_within_spread() {
+        let mut baseline = timing(day!(1), Some("10ms"), Some("3µs"));
+        baseline.spreads =
+            BTreeMap::from([("part_1".into(), 1_000_000.0), ("part_2".into(), 10.0)]);
+        let mut current = timing(day!(1), Some("9ms"), Some("2.5µs"));
+        current.spreads = BTreeMap::from([("part_1".into(), 500_000.0), ("part_2".into(), 10.0)]);
+
+        let deltas = compare(
+            &Timings {
+                data: vec![baseline],
+            },
+            &Timings {
+                data: vec![current.clone()],
+            },
+        );
+        // NOTE: -10% is within the spread of part 1, part 2 changes by less than the absolute floor.
+        assert_eq!(deltas[0].significance(), Significance::Noise);
+        assert_eq!(deltas[1].significance(), Significance::Noise);
+
+        let mut stable = timing(day!(1), Some("10ms"), Some("3µs"));
+        stable.spreads = BTreeMap::from([("part_1".into(), 100_000.0), ("part_2".into(), 10.0)]);
+        let deltas = compare(
+            &Timings { data: vec![stable] },
+            &Timings {
+                data: vec![current],
+            },
+        );
+        assert_eq!(deltas[0].significance(), Significance::Faster);
+    }
+}
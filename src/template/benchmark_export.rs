@@ -0,0 +1,352 @@
+/// Module that renders stored timings in formats that can be shared or compared outside the readme.
+use std::{collections::HashMap, fmt::Write, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::readme_benchmarks::{markdown_table, Context};
+use crate::template::timings::{parse_duration_nanos, Timing, Timings, STEPS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected one of: csv, json, markdown, html."
+            )),
+        }
+    }
+}
+
+/// Renders timings in `format`. The markdown table uses the same layout as the readme, see [`Context`].
+#[must_use]
+pub fn render(timings: &Timings, format: Format, context: &Context) -> String {
+    match format {
+        Format::Csv => render_csv(timings),
+        Format::Json => render_json(timings),
+        Format::Markdown => render_markdown(timings, context),
+        Format::Html => render_html(timings),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Measurements of one step of a day.
+struct Step<'a> {
+    time: Option<&'a str>,
+    nanos: Option<f64>,
+    peak: Option<&'a str>,
+    allocations: Option<u64>,
+}
+
+fn step<'a>(timing: &'a Timing, name: &str) -> Step<'a> {
+    let time = timing.step_time(name);
+    let memory = timing.memory.iter().find(|m| m.step == name);
+
+    Step {
+        time,
+        nanos: time.and_then(parse_duration_nanos),
+        peak: memory.map(|m| m.peak.as_str()),
+        allocations: memory.map(|m| m.allocations),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_csv(timings: &Timings) -> String {
+    let mut header = vec!["day".to_string()];
+    for name in STEPS {
+        header.extend([
+            name.to_string(),
+            format!("{name}_nanos"),
+            format!("{name}_peak"),
+            format!("{name}_allocations"),
+        ]);
+    }
+    header.push("total_nanos".into());
+
+    let mut lines = vec![header.join(",")];
+
+    for timing in &timings.data {
+        let mut cells = vec![timing.day.to_string()];
+        for name in STEPS {
+            let step = step(timing, name);
+            cells.extend([
+                step.time.map(csv_escape).unwrap_or_default(),
+                step.nanos.map(|n| n.to_string()).unwrap_or_default(),
+                step.peak.map(csv_escape).unwrap_or_default(),
+                step.allocations.map(|n| n.to_string()).unwrap_or_default(),
+            ]);
+        }
+        cells.push(timing.total_nanos.to_string());
+        lines.push(cells.join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_json(timings: &Timings) -> String {
+    let optional_string =
+        |value: Option<&str>| value.map_or(JsonValue::Null, |v| JsonValue::String(v.to_string()));
+    let optional_number = |value: Option<f64>| value.map_or(JsonValue::Null, JsonValue::Number);
+
+    let days = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(timing.day.to_string()));
+            map.insert("total_nanos".into(), JsonValue::Number(timing.total_nanos));
+
+            for name in STEPS {
+                let step = step(timing, name);
+                let value = if step.time.is_none() && step.peak.is_none() {
+                    JsonValue::Null
+                } else {
+                    let mut step_map: HashMap<String, JsonValue> = HashMap::new();
+                    step_map.insert("time".into(), optional_string(step.time));
+                    step_map.insert("nanos".into(), optional_number(step.nanos));
+                    step_map.insert("peak".into(), optional_string(step.peak));
+                    // NOTE: JSON numbers are floats, precision loss only affects absurd allocation counts.
+                    #[allow(clippy::cast_precision_loss)]
+                    step_map.insert(
+                        "allocations".into(),
+                        optional_number(step.allocations.map(|n| n as f64)),
+                    );
+                    JsonValue::Object(step_map)
+                };
+                map.insert(name.into(), value);
+            }
+
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "total_nanos".into(),
+        JsonValue::Number(timings.total_millis() * 1_000_000_f64),
+    );
+    map.insert("data".into(), JsonValue::Array(days));
+
+    // NOTE: formatting a JSON value built from strings and numbers can't fail.
+    JsonValue::Object(map).format().unwrap_or_default() + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_markdown(timings: &Timings, context: &Context) -> String {
+    let mut lines = markdown_table(timings, context);
+    lines.push(String::new());
+    lines.push(format!(
+        "**Total: {}**",
+        context.config.total_unit.format(timings.total_millis())
+    ));
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_html(timings: &Timings) -> String {
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0_f64, f64::max);
+
+    let mut rows = String::new();
+    for timing in &timings.data {
+        let share = if max_nanos > 0.0 {
+            timing.total_nanos / max_nanos * 100.0
+        } else {
+            0.0
+        };
+
+        let cells: String = STEPS
+            .iter()
+            .map(|name| {
+                let step = step(timing, name);
+                let time = step.time.map_or_else(|| "-".into(), html_escape);
+                match step.peak {
+                    Some(peak) => {
+                        format!("<td>{time}<br><small>{}</small></td>", html_escape(peak))
+                    }
+                    None => format!("<td>{time}</td>"),
+                }
+            })
+            .collect();
+
+        let _ = writeln!(
+            rows,
+            "<tr><th>Day {}</th>{cells}<td class=\"chart\"><div class=\"bar\" style=\"width: {share:.1}%\"></div><span>{:.3}ms</span></td></tr>",
+            timing.day.into_inner(),
+            timing.total_nanos / 1_000_000_f64,
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code Benchmarks</title>
+<style>
+  body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #1b1b1b; }}
+  table {{ border-collapse: collapse; width: 100%; }}
+  th, td {{ padding: 0.4rem 0.6rem; border-bottom: 1px solid #ddd; text-align: left; white-space: nowrap; }}
+  small {{ color: #666; }}
+  td.chart {{ width: 40%; position: relative; }}
+  .bar {{ display: inline-block; height: 1rem; background: #2f7d32; vertical-align: middle; margin-right: 0.5rem; }}
+</style>
+</head>
+<body>
+<h1>Benchmarks</h1>
+<table>
+<thead><tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Combined</th><th>Total</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+<p><strong>Total: {total:.2}ms</strong></p>
+</body>
+</html>
+"#,
+        total = timings.total_millis()
+    )
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{render, Context, Format};
+    use crate::{
+        day,
+        template::timings::{MemoryUsage, Timing, Timings},
+    };
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: Some("20µs".into()),
+                    parse: None,
+                    solve: None,
+                    total_nanos: 10_020_000_f64,
+                    phases: vec![],
+                    memory: vec![MemoryUsage {
+                        step: "part_1".into(),
+                        peak: "1.2 KiB".into(),
+                        allocations: 42,
+                        allocated: "5.0 KiB".into(),
+                    }],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
+                },
+                Timing {
+                    day: day!(6),
+                    part_1: None,
+                    part_2: None,
+                    parse: Some("1ms".into()),
+                    solve: Some("4ms".into()),
+                    total_nanos: 5_000_000_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("CSV"), Ok(Format::Csv));
+        assert_eq!(Format::from_str("md"), Ok(Format::Markdown));
+        assert_eq!(Format::from_str("xml").is_err(), true);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&get_mock_timings(), Format::Csv, &Context::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0].starts_with("day,parse,parse_nanos,parse_peak"),
+            true
+        );
+        assert_eq!(
+            lines[1],
+            "01,,,,,10ms,10000000,1.2 KiB,42,20µs,20000,,,,,,,10020000"
+        );
+        assert_eq!(lines[2], "06,1ms,1000000,,,,,,,,,,,4ms,4000000,,,5000000");
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&get_mock_timings(), Format::Json, &Context::default());
+        let value: JsonValue = json.parse().unwrap();
+        let data: &Vec<JsonValue> = value["data"].get().unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0]["part_1"]["nanos"], JsonValue::Number(10_000_000.0));
+        assert_eq!(data[0]["part_1"]["allocations"], JsonValue::Number(42.0));
+        assert_eq!(data[0]["solve"], JsonValue::Null);
+        assert_eq!(data[1]["solve"]["time"], JsonValue::String("4ms".into()));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = render(&get_mock_timings(), Format::Markdown, &Context::default());
+        assert_eq!(
+            markdown.contains("| [Day 6](./src/bin/06.rs) | `4ms` |"),
+            true
+        );
+        assert_eq!(markdown.ends_with("**Total: 15.02ms**\n"), true);
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render(&get_mock_timings(), Format::Html, &Context::default());
+        assert_eq!(html.starts_with("<!DOCTYPE html>"), true);
+        assert_eq!(html.contains("style=\"width: 100.0%\""), true);
+        assert_eq!(html.contains("style=\"width: 49.9%\""), true);
+        assert_eq!(html.contains("<br><small>1.2 KiB</small>"), true);
+    }
+}
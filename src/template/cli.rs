@@ -0,0 +1,868 @@
+/// Description of the command line interface, used for help texts, argument
+/// validation and shell completions of the cargo aliases.
+use std::{fmt::Write, str::FromStr};
+
+/// Kind of value an argument takes, used to offer completions.
+#[derive(Clone, Copy, Debug)]
+pub enum ValueKind {
+    Text,
+    Day,
+    Path,
+    OneOf(&'static [&'static str]),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Value {
+    pub name: &'static str,
+    pub kind: ValueKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Positional {
+    pub value: Value,
+    pub required: bool,
+    pub help: &'static str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Flag {
+    pub long: &'static str,
+    pub short: Option<&'static str>,
+    /// The value of flags like `--submit <part>`.
+    pub value: Option<Value>,
+    /// Whether the flag may be passed more than once.
+    pub repeatable: bool,
+    pub help: &'static str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positional: Option<Positional>,
+    pub flags: &'static [Flag],
+}
+
+impl Command {
+    /// Whether the command is available as cargo alias, e.g. `cargo solve`.
+    fn is_alias(&self) -> bool {
+        self.name != "help"
+    }
+
+    fn invocation(&self) -> String {
+        if self.is_alias() {
+            format!("cargo {}", self.name)
+        } else {
+            format!("cargo run --quiet -- {}", self.name)
+        }
+    }
+
+    /// Looks up a flag by its long or short name. `--flag=value` is matched as well.
+    #[must_use]
+    pub fn flag(&self, arg: &str) -> Option<&Flag> {
+        let name = arg.split_once('=').map_or(arg, |(name, _)| name);
+        self.flags
+            .iter()
+            .chain([&HELP])
+            .find(|flag| flag.long == name || flag.short == Some(name))
+    }
+}
+
+const DAY: Value = Value {
+    name: "day",
+    kind: ValueKind::Day,
+};
+
+const DAY_ARGUMENT: Positional = Positional {
+    value: DAY,
+    required: true,
+    help: "Day of the puzzle, from 1 to 25.",
+};
+
+const RELEASE: Flag = Flag {
+    long: "--release",
+    short: None,
+    value: None,
+    repeatable: false,
+    help: "Compile with optimizations.",
+};
+
+const VERBOSE: Flag = Flag {
+    long: "--verbose",
+    short: Some("-v"),
+    value: None,
+    repeatable: true,
+    help: "Show debug messages, pass twice to show trace messages.",
+};
+
+const HELP: Flag = Flag {
+    long: "--help",
+    short: Some("-h"),
+    value: None,
+    repeatable: false,
+    help: "Print the usage of the command.",
+};
+
+pub const PARTS: &[&str] = &["1", "2"];
+pub const EXPORT_FORMATS: &[&str] = &["csv", "json", "markdown", "html"];
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+static COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution module, input and example file for a day.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[
+            Flag {
+                long: "--download",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Download the puzzle input and description as well.",
+            },
+            Flag {
+                long: "--overwrite",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Overwrite an existing solution module.",
+            },
+            Flag {
+                long: "--examples",
+                short: None,
+                value: Some(Value {
+                    name: "count",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Number of example files to create, with tests for each of them.",
+            },
+        ],
+    },
+    Command {
+        name: "add-example",
+        about: "Add an example file to a day together with a test for it.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[
+            Flag {
+                long: "--part",
+                short: None,
+                value: Some(Value {
+                    name: "part",
+                    kind: ValueKind::OneOf(PARTS),
+                }),
+                repeatable: false,
+                help: "Part the test runs, defaults to 1.",
+            },
+            Flag {
+                long: "--expect",
+                short: None,
+                value: Some(Value {
+                    name: "answer",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Expected answer of the test, defaults to None.",
+            },
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download the puzzle input and description of a day with aoc-cli.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[],
+    },
+    Command {
+        name: "read",
+        about: "Print the puzzle description of a day in the terminal.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of a day against the puzzle input.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[
+            RELEASE,
+            Flag {
+                long: "--dhat",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Profile heap allocations with DHAT.",
+            },
+            Flag {
+                long: "--submit",
+                short: None,
+                value: Some(Value {
+                    name: "part",
+                    kind: ValueKind::OneOf(PARTS),
+                }),
+                repeatable: false,
+                help: "Submit the answer of a part after solving.",
+            },
+            Flag {
+                long: "--variant",
+                short: None,
+                value: Some(Value {
+                    name: "name",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Run another variant of the parts that declare it.",
+            },
+            Flag {
+                long: "--param",
+                short: None,
+                value: Some(Value {
+                    name: "name=value",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: true,
+                help: "Override a puzzle parameter declared with `params!`.",
+            },
+            VERBOSE,
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run the solutions of all days.",
+        positional: None,
+        flags: &[
+            RELEASE,
+            VERBOSE,
+            Flag {
+                long: "--log-day",
+                short: None,
+                value: Some(DAY),
+                repeatable: true,
+                help: "Only show messages of this day, can be passed multiple times.",
+            },
+        ],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark solutions, by default only days without stored timings.",
+        positional: Some(Positional {
+            value: DAY,
+            required: false,
+            help: "Only benchmark this day.",
+        }),
+        flags: &[
+            Flag {
+                long: "--all",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Benchmark all days, including days with stored timings.",
+            },
+            Flag {
+                long: "--store",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Store the timings and update the benchmark table in the readme.",
+            },
+            Flag {
+                long: "--memory",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Track heap allocations in a separate DHAT run.",
+            },
+            Flag {
+                long: "--variants",
+                short: None,
+                value: None,
+                repeatable: false,
+                help: "Benchmark and compare all variants of a day.",
+            },
+            Flag {
+                long: "--save-baseline",
+                short: None,
+                value: Some(Value {
+                    name: "name",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Save the timings as a named baseline.",
+            },
+            Flag {
+                long: "--baseline",
+                short: None,
+                value: Some(Value {
+                    name: "name",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Compare the timings against a named baseline.",
+            },
+            Flag {
+                long: "--export",
+                short: None,
+                value: Some(Value {
+                    name: "format",
+                    kind: ValueKind::OneOf(EXPORT_FORMATS),
+                }),
+                repeatable: false,
+                help: "Export the stored timings instead of benchmarking.",
+            },
+            Flag {
+                long: "--out",
+                short: None,
+                value: Some(Value {
+                    name: "path",
+                    kind: ValueKind::Path,
+                }),
+                repeatable: false,
+                help: "Write the export to a file instead of stdout.",
+            },
+        ],
+    },
+    Command {
+        name: "stress",
+        about: "Run a solution on generated inputs and estimate how it scales.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[
+            Flag {
+                long: "--sizes",
+                short: None,
+                value: Some(Value {
+                    name: "sizes",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Comma separated input sizes, defaults to `10,100,1000`.",
+            },
+            Flag {
+                long: "--seed",
+                short: None,
+                value: Some(Value {
+                    name: "seed",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Seed of the generated inputs, defaults to 0.",
+            },
+        ],
+    },
+    Command {
+        name: "fuzz",
+        about: "Feed mutated examples into a solution and save the inputs that panic.",
+        positional: Some(DAY_ARGUMENT),
+        flags: &[
+            Flag {
+                long: "--iterations",
+                short: None,
+                value: Some(Value {
+                    name: "iterations",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Number of mutated inputs, defaults to 10000.",
+            },
+            Flag {
+                long: "--seed",
+                short: None,
+                value: Some(Value {
+                    name: "seed",
+                    kind: ValueKind::Text,
+                }),
+                repeatable: false,
+                help: "Seed of the mutations, random by default.",
+            },
+            RELEASE,
+        ],
+    },
+    Command {
+        name: "progress",
+        about: "Show earned stars, based on verified answers and submissions.",
+        positional: None,
+        flags: &[Flag {
+            long: "--store",
+            short: None,
+            value: None,
+            repeatable: false,
+            help: "Update the star table in the readme.",
+        }],
+    },
+    #[cfg(feature = "today")]
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the puzzle of today.",
+        positional: None,
+        flags: &[],
+    },
+    Command {
+        name: "completions",
+        about: "Print a completion script for the cargo aliases.",
+        positional: Some(Positional {
+            value: Value {
+                name: "shell",
+                kind: ValueKind::OneOf(SHELLS),
+            },
+            required: true,
+            help: "Shell to generate the script for.",
+        }),
+        flags: &[],
+    },
+    Command {
+        name: "help",
+        about: "Print the usage of all commands or of a single command.",
+        positional: Some(Positional {
+            value: Value {
+                name: "command",
+                kind: ValueKind::Text,
+            },
+            required: false,
+            help: "Command to print the usage of.",
+        }),
+        flags: &[],
+    },
+];
+
+#[must_use]
+pub fn commands() -> &'static [Command] {
+    COMMANDS
+}
+
+#[must_use]
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// Suggests the candidate closest to a mistyped input, e.g. `--store` for `--stroe`.
+#[must_use]
+pub fn suggest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Finds the first argument that looks like a flag but is not accepted by the command.
+/// Returns an error message with a suggestion if there is a similar flag.
+#[must_use]
+pub fn check_flags(command: &Command, args: &[String]) -> Option<String> {
+    let unknown = args
+        .iter()
+        .find(|arg| arg.starts_with('-') && command.flag(arg).is_none())?;
+
+    let names = command
+        .flags
+        .iter()
+        .flat_map(|flag| [Some(flag.long), flag.short])
+        .flatten();
+
+    let hint = match suggest(unknown, names) {
+        Some(flag) => format!(" Did you mean `{flag}`?"),
+        None => String::new(),
+    };
+
+    Some(format!(
+        "unknown option `{unknown}` for `{}`.{hint}",
+        command.name
+    ))
+}
+
+fn positional_usage(positional: &Positional) -> String {
+    if positional.required {
+        format!("<{}>", positional.value.name)
+    } else {
+        format!("[<{}>]", positional.value.name)
+    }
+}
+
+fn flag_usage(flag: &Flag) -> String {
+    let mut usage = match flag.short {
+        Some(short) => format!("{short}, {}", flag.long),
+        None => format!("    {}", flag.long),
+    };
+    if let Some(value) = flag.value {
+        let _ = write!(usage, " <{}>", value.name);
+    }
+    usage
+}
+
+fn with_values(help: &str, kind: ValueKind) -> String {
+    match kind {
+        ValueKind::OneOf(values) => format!("{help} [possible values: {}]", values.join(", ")),
+        _ => help.into(),
+    }
+}
+
+/// Usage text of a single command, printed for `--help` and for invalid arguments.
+#[must_use]
+pub fn usage(command: &Command) -> String {
+    let mut s = format!("{}\n\nUsage: {}", command.about, command.invocation());
+    if let Some(positional) = &command.positional {
+        let _ = write!(s, " {}", positional_usage(positional));
+    }
+    s.push_str(" [options]\n");
+
+    let flags: Vec<(String, String)> = command
+        .flags
+        .iter()
+        .chain([&HELP])
+        .map(|flag| {
+            let help = match flag.value {
+                Some(value) => with_values(flag.help, value.kind),
+                None => flag.help.into(),
+            };
+            (flag_usage(flag), help)
+        })
+        .collect();
+
+    let width = flags
+        .iter()
+        .map(|(usage, _)| usage.len())
+        .chain(command.positional.iter().map(|p| positional_usage(p).len()))
+        .max()
+        .unwrap_or_default()
+        + 2;
+
+    if let Some(positional) = &command.positional {
+        let _ = write!(
+            s,
+            "\nArguments:\n  {:<width$}{}\n",
+            positional_usage(positional),
+            with_values(positional.help, positional.value.kind)
+        );
+    }
+
+    s.push_str("\nOptions:\n");
+    for (usage, help) in flags {
+        let _ = writeln!(s, "  {usage:<width$}{help}");
+    }
+
+    s
+}
+
+/// Overview of all commands, printed for `help` and when no command is given.
+#[must_use]
+pub fn help() -> String {
+    let mut s = String::from("Usage: cargo <command> [options]\n\nCommands:\n");
+    for command in commands() {
+        let _ = writeln!(s, "  {:<14}{}", command.name, command.about);
+    }
+    s.push_str("\nRun `cargo <command> --help` to print the options of a command.\n");
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!(
+                "unknown shell `{s}`, expected one of: {}.",
+                SHELLS.join(", ")
+            )),
+        }
+    }
+}
+
+/// Completion script for the cargo aliases. Other cargo commands are still completed by
+/// the completions of cargo itself, if they are installed.
+#[must_use]
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn aliases() -> impl Iterator<Item = &'static Command> {
+    commands().iter().filter(|command| command.is_alias())
+}
+
+fn days() -> Vec<String> {
+    (1..=25).map(|day: u8| day.to_string()).collect()
+}
+
+fn value_words(kind: ValueKind) -> Vec<String> {
+    match kind {
+        ValueKind::Day => days(),
+        ValueKind::OneOf(values) => values.iter().map(|v| (*v).to_string()).collect(),
+        ValueKind::Text | ValueKind::Path => vec![],
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn bash_completions() -> String {
+    let mut cases = String::new();
+
+    for command in aliases() {
+        let _ = writeln!(cases, "            {})", command.name);
+
+        let value_flags: Vec<&Flag> = command.flags.iter().filter(|f| f.value.is_some()).collect();
+        if !value_flags.is_empty() {
+            cases.push_str("                case \"$prev\" in\n");
+            for flag in value_flags {
+                let kind = flag.value.map_or(ValueKind::Text, |v| v.kind);
+                let compgen = match kind {
+                    ValueKind::Path => "-f".into(),
+                    _ => format!("-W \"{}\"", value_words(kind).join(" ")),
+                };
+                let _ = writeln!(
+                    cases,
+                    "                    {}) COMPREPLY=($(compgen {compgen} -- \"$cur\")); return ;;",
+                    flag.long
+                );
+            }
+            cases.push_str("                esac\n");
+        }
+
+        let mut words: Vec<String> = command
+            .positional
+            .map(|p| value_words(p.value.kind))
+            .unwrap_or_default();
+        for flag in command.flags.iter().chain([&HELP]) {
+            words.extend(flag.short.map(String::from));
+            words.push(flag.long.into());
+        }
+        let _ = writeln!(cases, "                words=\"{}\"", words.join(" "));
+        cases.push_str("                ;;\n");
+    }
+
+    format!(
+        r#"# Completions for the cargo aliases of this project.
+# Load them with `source <(cargo completions bash)`.
+_advent_of_code() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local words=""
+    if [[ $COMP_CWORD -ge 2 ]]; then
+        case "${{COMP_WORDS[1]}}" in
+{cases}        esac
+    fi
+    if [[ -n "$words" ]]; then
+        COMPREPLY=($(compgen -W "$words" -- "$cur"))
+    elif declare -F _cargo >/dev/null; then
+        _cargo "$@"
+    fi
+}}
+if ! declare -F _cargo >/dev/null && declare -F _completion_loader >/dev/null; then
+    _completion_loader cargo
+fi
+complete -F _advent_of_code cargo
+"#
+    )
+}
+
+fn zsh_action(kind: ValueKind) -> String {
+    match kind {
+        ValueKind::Path => "_files".into(),
+        ValueKind::Text => " ".into(),
+        _ => format!("({})", value_words(kind).join(" ")),
+    }
+}
+
+fn zsh_completions() -> String {
+    let mut cases = String::new();
+
+    for command in aliases() {
+        let _ = writeln!(cases, "            {})", command.name);
+        cases.push_str("                shift words; (( CURRENT-- ))\n");
+        cases.push_str("                _arguments -s");
+
+        if let Some(positional) = &command.positional {
+            let colons = if positional.required { ":" } else { "::" };
+            let spec = format!(
+                "{colons}{}:{}",
+                positional.value.name,
+                zsh_action(positional.value.kind)
+            );
+            let _ = write!(cases, " \\\n                    {}", quote(&spec));
+        }
+
+        for flag in command.flags.iter().chain([&HELP]) {
+            let help = flag.help.replace('[', r"\[").replace(']', r"\]");
+            let mut spec = format!("[{help}]");
+            if let Some(value) = flag.value {
+                let _ = write!(spec, ":{}:{}", value.name, zsh_action(value.kind));
+            }
+
+            let names = match flag.short {
+                Some(short) if flag.repeatable => {
+                    format!("'*'{{{short},{}}}", flag.long)
+                }
+                Some(short) => format!("'({short} {})'{{{short},{}}}", flag.long, flag.long),
+                None if flag.repeatable => format!("'*{}'", flag.long),
+                None => flag.long.into(),
+            };
+            let _ = write!(cases, " \\\n                    {names}{}", quote(&spec));
+        }
+
+        cases.push_str("\n                return\n                ;;\n");
+    }
+
+    format!(
+        r#"# Completions for the cargo aliases of this project.
+# Load them with `source <(cargo completions zsh)` after `compinit`.
+_advent_of_code() {{
+    if (( CURRENT > 2 )); then
+        case ${{words[2]}} in
+{cases}        esac
+    fi
+    (( $+functions[_cargo] )) && _cargo "$@"
+}}
+compdef _advent_of_code cargo
+"#
+    )
+}
+
+fn fish_completions() -> String {
+    let mut s = String::from(
+        "# Completions for the cargo aliases of this project.\n\
+         # Load them with `cargo completions fish | source`.\n",
+    );
+
+    for command in aliases() {
+        let _ = writeln!(
+            s,
+            "complete -c cargo -n __fish_use_subcommand -f -a {} -d {}",
+            command.name,
+            quote(command.about)
+        );
+    }
+
+    for command in aliases() {
+        let condition = quote(&format!("__fish_seen_subcommand_from {}", command.name));
+
+        if let Some(positional) = &command.positional {
+            let _ = writeln!(
+                s,
+                "complete -c cargo -n {condition} -f -a {} -d {}",
+                quote(&value_words(positional.value.kind).join(" ")),
+                quote(positional.help)
+            );
+        }
+
+        for flag in command.flags.iter().chain([&HELP]) {
+            let _ = write!(s, "complete -c cargo -n {condition}");
+            if let Some(short) = flag.short {
+                let _ = write!(s, " -s {}", short.trim_start_matches('-'));
+            }
+            let _ = write!(s, " -l {}", flag.long.trim_start_matches('-'));
+            match flag.value.map(|v| v.kind) {
+                Some(ValueKind::Path) => s.push_str(" -r -F"),
+                Some(kind) => {
+                    let _ = write!(s, " -x -a {}", quote(&value_words(kind).join(" ")));
+                }
+                None => {}
+            }
+            let _ = writeln!(s, " -d {}", quote(flag.help));
+        }
+    }
+
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_flags() {
+        let time = find("time").unwrap();
+        assert_eq!(time.flag("--store").is_some(), true);
+        assert_eq!(time.flag("--export=csv").is_some(), true);
+        assert_eq!(time.flag("-h").is_some(), true);
+        assert_eq!(time.flag("--release").is_some(), false);
+        assert_eq!(find("solve").unwrap().flag("-v").is_some(), true);
+    }
+
+    #[test]
+    fn suggests_similar_names() {
+        assert_eq!(suggest("tmie", ["time", "solve"]), Some("time"));
+        assert_eq!(suggest("--stroe", ["--all", "--store"]), Some("--store"));
+        assert_eq!(suggest("benchmark", ["time", "solve"]), None);
+    }
+
+    #[test]
+    fn checks_flags() {
+        let time = find("time").unwrap();
+        let args = |args: &[&str]| args.iter().map(|a| (*a).to_string()).collect::<Vec<_>>();
+
+        assert_eq!(check_flags(time, &args(&["time", "1", "--store"])), None);
+        assert_eq!(
+            check_flags(time, &args(&["time", "1", "--stroe"])),
+            Some("unknown option `--stroe` for `time`. Did you mean `--store`?".into())
+        );
+        assert_eq!(
+            check_flags(time, &args(&["time", "--xyz"])),
+            Some("unknown option `--xyz` for `time`.".into())
+        );
+    }
+
+    #[test]
+    fn renders_usage() {
+        let usage = usage(find("solve").unwrap());
+        assert_eq!(usage.contains("Usage: cargo solve <day> [options]"), true);
+        assert_eq!(usage.contains("-v, --verbose"), true);
+        assert_eq!(usage.contains("--submit <part>"), true);
+        assert_eq!(usage.contains("[possible values: 1, 2]"), true);
+
+        let help = help();
+        assert_eq!(commands().iter().all(|c| help.contains(c.name)), true);
+    }
+
+    #[test]
+    fn renders_completions() {
+        let bash = completions(Shell::Bash);
+        assert_eq!(bash.contains("complete -F _advent_of_code cargo"), true);
+        assert_eq!(
+            bash.contains("--submit) COMPREPLY=($(compgen -W \"1 2\" -- \"$cur\")); return ;;"),
+            true
+        );
+
+        let zsh = completions(Shell::Zsh);
+        assert_eq!(zsh.contains("'*'{-v,--verbose}'[Show debug messages"), true);
+        assert_eq!(zsh.contains("--export'[Export the stored timings"), true);
+
+        let fish = completions(Shell::Fish);
+        assert_eq!(
+            fish.contains("-n '__fish_seen_subcommand_from time' -l out -r -F"),
+            true
+        );
+        assert_eq!(fish.contains("-a help"), false);
+    }
+}
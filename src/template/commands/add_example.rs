@@ -0,0 +1,56 @@
+use std::fs::{self, OpenOptions};
+
+use crate::template::config;
+use crate::template::example_tests::{example_test, insert_test, test_name};
+use crate::template::examples::{uses_manifest, Example, Manifest};
+use crate::template::{Day, Error};
+
+/// Creates the next free example file `NN-N.txt` and adds it to the manifest of `day`, or
+/// adds a test for it to the solution if it does not use [`crate::example_tests!`].
+pub fn handle(day: Day, part: u8, expected: Option<&str>) -> Result<(), Error> {
+    let module_path = format!("src/bin/{day}.rs");
+    let source = fs::read_to_string(&module_path).map_err(|e| {
+        Error::io(
+            format!("Failed to read \"{module_path}\", run `cargo scaffold {day}` first"),
+            e,
+        )
+    })?;
+
+    let paths = &config::get().paths;
+    let example = (2..=u8::MAX)
+        .find(|example| !paths.example_part(day, *example).exists())
+        .ok_or_else(|| Error::Other(format!("Too many example files for day {day}.")))?;
+    let example_path = paths.example_part(day, example).display().to_string();
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&example_path)
+        .map_err(|e| Error::io("Failed to create example file", e))?;
+    println!("Created empty example file \"{example_path}\"");
+
+    if uses_manifest(&source) {
+        let manifest_path = paths.example_manifest(day).display().to_string();
+        let expected = expected.map(String::from);
+        let example = Example {
+            file: format!("{day}-{example}.txt"),
+            part_1: if part == 1 { expected.clone() } else { None },
+            part_2: if part == 2 { expected } else { None },
+            params: vec![],
+        };
+        Manifest::append(day, &example)
+            .map_err(|e| Error::io("Failed to add example to manifest", e))?;
+        println!("Added `{}` to \"{manifest_path}\"", example.file);
+    } else {
+        let source = insert_test(&source, &example_test(&source, part, example, expected));
+        fs::write(&module_path, source).map_err(|e| Error::io("Failed to add test", e))?;
+        println!(
+            "Added test `{}` to \"{module_path}\"",
+            test_name(part, example)
+        );
+    }
+
+    println!("---");
+    println!("🎄 Paste the example into the file and run `cargo test --bin {day}`.");
+    Ok(())
+}
@@ -1,5 +1,7 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, log, run_multi::run_multi, Day, Error};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, verbosity: u8, log_days: &[Day]) -> Result<(), Error> {
+    let log_args = log::forward_args(verbosity, log_days);
+    let (_, result) = run_multi(&all_days().collect(), is_release, false, false, &log_args);
+    result
 }
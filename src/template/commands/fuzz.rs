@@ -0,0 +1,38 @@
+use std::process::{Command, Stdio};
+
+use crate::template::fuzz::Settings;
+use crate::template::{Day, Error};
+
+/// Fuzzes the parse path of a day, see [`crate::template::fuzz`]. Runs a debug build unless `release`
+/// is set, so that overflows panic as well.
+pub fn handle(day: Day, iterations: usize, seed: u64, release: bool) -> Result<(), Error> {
+    let settings = Settings { iterations, seed };
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+    cmd_args.push("--".to_string());
+    cmd_args.extend(settings.to_args());
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::ChildFailed {
+            command: format!("cargo {}", cmd_args.join(" ")),
+            status,
+        })
+    }
+}
@@ -1,6 +1,10 @@
+pub mod add_example;
 pub mod all;
 pub mod download;
+pub mod fuzz;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stress;
 pub mod time;
@@ -0,0 +1,20 @@
+use crate::template::progress::{self, Progress};
+use crate::template::{aoc_cli, Error};
+
+pub fn handle(store: bool) -> Result<(), Error> {
+    let progress = Progress::from_local_data();
+
+    for line in progress.calendar() {
+        println!("{line}");
+    }
+    println!();
+    println!("{}", progress.summary());
+
+    if store {
+        progress::update(&progress, aoc_cli::get_year())?;
+        println!();
+        println!("Stored updated progress.");
+    }
+
+    Ok(())
+}
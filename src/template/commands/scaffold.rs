@@ -1,14 +1,31 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
 };
 
-use crate::template::Day;
+use crate::template::config::{self, Template};
+use crate::template::example_tests::{example_test, insert_test};
+use crate::template::examples::{uses_manifest, Manifest};
+use crate::template::{Day, Error};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+const SOLUTION_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/template_solution.txt"
+));
+
+/// The module template selected with `scaffold.template` in `aoc.toml`.
+fn module_template() -> Result<String, Error> {
+    match &config::get().scaffold.template {
+        Template::Functions => Ok(MODULE_TEMPLATE.into()),
+        Template::Solution => Ok(SOLUTION_TEMPLATE.into()),
+        Template::File(path) => fs::read_to_string(path)
+            .map_err(|e| Error::io(format!("Failed to read template \"{}\"", path.display()), e)),
+    }
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -27,53 +44,62 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+/// Creates the module, input and example files of a day. With `examples` > 1, additional
+/// example files `NN-2.txt`, `NN-3.txt`, ... are created together with tests for them.
+pub fn handle(day: Day, overwrite: bool, examples: u8) -> Result<(), Error> {
+    let paths = &config::get().paths;
+    let input_path = paths.input(day).display().to_string();
     let module_path = format!("src/bin/{day}.rs");
+    let example_numbers = 1..=examples.max(1);
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let mut module = module_template()?
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY_PADDED%", &day.to_string());
+    let uses_manifest = uses_manifest(&module);
+    if !uses_manifest {
+        for example in 2..=examples {
+            for part in 1..=2 {
+                module = insert_test(&module, &example_test(&module, part, example, None));
+            }
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(|e| Error::io("Failed to create module file", e))?;
+
+    file.write_all(module.as_bytes())
+        .map_err(|e| Error::io("Failed to write module contents", e))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path).map_err(|e| Error::io("Failed to create input file", e))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    let example_paths = example_numbers.clone().map(|example| match example {
+        1 => paths.example(day),
+        _ => paths.example_part(day, example),
+    });
+    for example_path in example_paths {
+        let example_path = example_path.display().to_string();
+        create_file(&example_path).map_err(|e| Error::io("Failed to create example file", e))?;
+        println!("Created empty example file \"{}\"", &example_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if uses_manifest {
+        let manifest_path = paths.example_manifest(day).display().to_string();
+        let manifest = Manifest::with_files(example_numbers.map(|example| match example {
+            1 => format!("{day}.txt"),
+            _ => format!("{day}-{example}.txt"),
+        }))
+        .to_toml()
+        .map_err(Error::Other)?;
+
+        safe_create_file(&manifest_path, overwrite)
+            .and_then(|mut file| file.write_all(manifest.as_bytes()))
+            .map_err(|e| Error::io("Failed to create example manifest", e))?;
+        println!("Created example manifest \"{}\"", &manifest_path);
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+    Ok(())
 }
@@ -1,8 +1,23 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::params::{self, Override};
+use crate::template::{log, Day, Error};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    variant: Option<&str>,
+    params: &[Override],
+    verbosity: u8,
+) -> Result<(), Error> {
+    if submit_part.is_some() && !params.is_empty() {
+        return Err(Error::Usage(
+            "Answers computed with `--param` can not be submitted.".into(),
+        ));
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,12 +38,27 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if let Some(variant) = variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant.to_string());
+    }
+
+    cmd_args.extend(params::to_args(params));
+    cmd_args.extend(log::forward_args(verbosity, &[]));
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::ChildFailed {
+            command: format!("cargo {}", cmd_args.join(" ")),
+            status,
+        })
+    }
 }
@@ -0,0 +1,35 @@
+use std::process::{Command, Stdio};
+
+use crate::template::stress::Settings;
+use crate::template::{Day, Error};
+
+/// Runs the release build of a day on generated inputs of each size, see [`crate::template::stress`].
+pub fn handle(day: Day, sizes: Vec<usize>, seed: u64) -> Result<(), Error> {
+    let settings = Settings { sizes, seed };
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+    ];
+    cmd_args.extend(settings.to_args());
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::ChildFailed {
+            command: format!("cargo {}", cmd_args.join(" ")),
+            status,
+        })
+    }
+}
@@ -1,15 +1,43 @@
 use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::baseline;
+use crate::template::benchmark_export::{self, Format};
+use crate::template::readme_benchmarks::Context;
+use crate::template::run_multi::{child_commands, run_multi};
+use crate::template::timings::{is_valid_baseline_name, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Error};
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    memory: bool,
+    save_baseline: Option<&str>,
+    compare_baseline: Option<&str>,
+) -> Result<(), Error> {
+    for name in save_baseline.iter().chain(compare_baseline.iter()) {
+        if !is_valid_baseline_name(name) {
+            return Err(Error::Usage(format!(
+                "Invalid baseline name `{name}`, only letters, digits, `-` and `_` are allowed."
+            )));
+        }
+    }
+
+    let baseline = compare_baseline
+        .map(Timings::read_baseline)
+        .transpose()
+        .map_err(Error::Other)?;
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if let Some(baseline) = &baseline {
+                // when comparing, re-run the days contained in the baseline.
+                baseline.data.iter().map(|t| t.day).collect()
+            } else if run_all {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,20 +49,78 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: days that failed are reported after the timings of the other days are saved.
+    let (timings, result) = run_multi(&days_to_run, true, true, memory, &[]);
+    let timings = timings.unwrap_or_default();
+
+    if let (Some(name), Some(baseline)) = (compare_baseline, &baseline) {
+        baseline::print_comparison(name, &baseline::compare(baseline, &timings));
+    }
+
+    if let Some(name) = save_baseline {
+        // NOTE: merge with an existing baseline, so it can be built up one day at a time.
+        let merged_baseline = Timings::read_baseline(name)
+            .unwrap_or_default()
+            .merge(&timings);
+
+        merged_baseline
+            .store_baseline(name)
+            .map_err(|e| Error::io(format!("Failed to save baseline `{name}`"), e))?;
+        println!();
+        println!("Saved baseline `{name}`.");
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(|e| Error::io("Failed to store timings", e))?;
 
+        readme_benchmarks::update(merged_timings)?;
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        println!("Stored updated benchmarks.");
+    }
+
+    result
+}
+
+/// Benchmarks every variant of a day and prints how they compare. Timings of variants are not stored.
+pub fn compare_variants(day: Option<Day>) -> Result<(), Error> {
+    let Some(day) = day else {
+        return Err(Error::Usage(
+            "`--variants` requires a day, e.g. `cargo time 11 --variants`.".into(),
+        ));
+    };
+
+    let output = child_commands::run_solution(day, true, true, &["--variants".into()])?;
+    if output.is_empty() {
+        println!("Not solved.");
+    }
+
+    Ok(())
+}
+
+/// Renders the stored timings in `format` and writes them to `out` or stdout.
+pub fn export(format: &str, out: Option<&Path>) -> Result<(), Error> {
+    let format: Format = format.parse().map_err(Error::Usage)?;
+
+    let timings = Timings::read_from_file();
+    if timings.data.is_empty() {
+        return Err(Error::Other(
+            "No stored timings found. Run `cargo time --store` first.".into(),
+        ));
+    }
+
+    let rendered = benchmark_export::render(&timings, format, &Context::load(&timings));
+
+    match out {
+        Some(path) => {
+            fs::write(path, rendered)
+                .map_err(|e| Error::io(format!("Failed to write \"{}\"", path.display()), e))?;
+            println!("Exported benchmarks to \"{}\".", path.display());
         }
+        None => print!("{rendered}"),
     }
+
+    Ok(())
 }
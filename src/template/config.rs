@@ -0,0 +1,592 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+///
+/// The file is parsed with the TOML subset of [`crate::template::toml`]. All keys are optional.
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::toml::{parse_toml, Value};
+use crate::template::Day;
+
+static CONFIG_FILE_PATH: &str = "./aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The project configuration, read from `aoc.toml` on first access.
+/// Exits with an error message if the file is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| match Config::read() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    })
+}
+
+/// Typed contents of `aoc.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// The year of the puzzles. Falls back to the `AOC_YEAR` environment variable.
+    pub year: Option<u16>,
+    pub paths: PathsConfig,
+    pub benchmark: BenchmarkConfig,
+    pub run: RunConfig,
+    pub scaffold: ScaffoldConfig,
+    pub readme: ReadmeConfig,
+}
+
+impl Config {
+    /// The configured year, or the one set with the `AOC_YEAR` environment variable.
+    #[must_use]
+    pub fn year(&self) -> Option<u16> {
+        self.year
+            .or_else(|| std::env::var("AOC_YEAR").ok()?.parse().ok())
+    }
+}
+
+/// Locations of the data files, see `[paths]` in `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathsConfig {
+    /// Folder of timings, answers and submissions, and parent of the input folders by default.
+    pub data: PathBuf,
+    pub inputs: Option<PathBuf>,
+    pub examples: Option<PathBuf>,
+    pub puzzles: Option<PathBuf>,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            data: PathBuf::from("data"),
+            inputs: None,
+            examples: None,
+            puzzles: None,
+        }
+    }
+}
+
+impl PathsConfig {
+    /// Folder of the files read with [`crate::template::read_file`], e.g. `inputs` or `examples`.
+    #[must_use]
+    pub fn folder(&self, name: &str) -> PathBuf {
+        let configured = match name {
+            "inputs" => self.inputs.as_ref(),
+            "examples" => self.examples.as_ref(),
+            "puzzles" => self.puzzles.as_ref(),
+            _ => None,
+        };
+        configured.cloned().unwrap_or_else(|| self.data.join(name))
+    }
+
+    #[must_use]
+    pub fn input(&self, day: Day) -> PathBuf {
+        self.folder("inputs").join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn example(&self, day: Day) -> PathBuf {
+        self.folder("examples").join(format!("{day}.txt"))
+    }
+
+    /// Additional example read with [`crate::template::read_file_part`], e.g. `01-2.txt`.
+    #[must_use]
+    pub fn example_part(&self, day: Day, part: u8) -> PathBuf {
+        self.folder("examples").join(format!("{day}-{part}.txt"))
+    }
+
+    /// Manifest of the examples of a day, e.g. `01.toml`.
+    #[must_use]
+    pub fn example_manifest(&self, day: Day) -> PathBuf {
+        self.folder("examples").join(format!("{day}.toml"))
+    }
+
+    #[must_use]
+    pub fn puzzle(&self, day: Day) -> PathBuf {
+        self.folder("puzzles").join(format!("{day}.md"))
+    }
+
+    /// Reproducers saved by `cargo fuzz`, e.g. `data/fuzz/15/`.
+    #[must_use]
+    pub fn fuzz(&self, day: Day) -> PathBuf {
+        self.folder("fuzz").join(day.to_string())
+    }
+
+    /// A JSON file in the data folder, e.g. `timings.json`.
+    #[must_use]
+    pub fn data_file(&self, name: &str) -> PathBuf {
+        self.data.join(name)
+    }
+}
+
+/// Settings of `cargo time`, see `[benchmark]` in `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchmarkConfig {
+    /// Approximate time spent benchmarking each part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+/// Settings of solution runs, see `[run]` in `aoc.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Solutions running longer than this are aborted.
+    pub timeout: Option<Duration>,
+}
+
+/// Settings of `cargo scaffold`, see `[scaffold]` in `aoc.toml`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScaffoldConfig {
+    pub template: Template,
+}
+
+/// The module template used by `cargo scaffold`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Template {
+    /// Free functions `part_one` and `part_two`.
+    #[default]
+    Functions,
+    /// A type implementing [`crate::template::Solution`].
+    Solution,
+    /// A custom template file, `%DAY_NUMBER%` is replaced by the day.
+    File(PathBuf),
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "functions" => Ok(Template::Functions),
+            "solution" => Ok(Template::Solution),
+            path if Path::new(path).extension().is_some() => Ok(Template::File(path.into())),
+            _ => Err(format!(
+                "unknown template `{s}`, expected `functions`, `solution` or the path of a template file."
+            )),
+        }
+    }
+}
+
+/// Layout of the benchmark table in the readme, see `[readme]` in `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    /// Columns after the day column, in order.
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Number of slowest days to highlight.
+    pub highlight_slowest: usize,
+    /// Link of the day column, `{day}` is replaced by the padded day.
+    pub day_link: String,
+    pub total_unit: TotalUnit,
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Part1, Column::Part2, Column::Memory],
+            sort: Sort::Day,
+            highlight_slowest: 0,
+            day_link: "./src/bin/{day}.rs".into(),
+            total_unit: TotalUnit::Millis,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The title of the puzzle, linked to its page.
+    Title,
+    Parse,
+    Part1,
+    Part2,
+    /// Heap usage of both parts, only shown once `cargo time --memory` recorded some.
+    Memory,
+    /// Number of benchmark samples.
+    Samples,
+    /// Whether the benchmarked answers match the verified answers in `data/answers.json`.
+    Verified,
+    Stars,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "title" => Ok(Column::Title),
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "memory" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            "verified" => Ok(Column::Verified),
+            "stars" => Ok(Column::Stars),
+            _ => Err(format!(
+                "unknown column `{s}`, expected one of: title, parse, part_1, part_2, memory, samples, verified, stars."
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sort {
+    Day,
+    /// Slowest days first.
+    Duration,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "duration" => Ok(Sort::Duration),
+            _ => Err(format!("unknown sort `{s}`, expected `day` or `duration`.")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TotalUnit {
+    Millis,
+    Seconds,
+}
+
+impl FromStr for TotalUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ms" => Ok(TotalUnit::Millis),
+            "s" => Ok(TotalUnit::Seconds),
+            _ => Err(format!("unknown unit `{s}`, expected `ms` or `s`.")),
+        }
+    }
+}
+
+impl TotalUnit {
+    #[must_use]
+    pub fn format(self, total_millis: f64) -> String {
+        match self {
+            TotalUnit::Millis => format!("{total_millis:.2}ms"),
+            TotalUnit::Seconds => format!("{:.2}s", total_millis / 1000.0),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml`. If not present, returns the default config.
+    pub fn read() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(content) => content
+                .parse()
+                .map_err(|e| format!("invalid {CONFIG_FILE_PATH}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("could not read {CONFIG_FILE_PATH}: {e}")),
+        }
+    }
+}
+
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = Values(parse_toml(s)?);
+        let mut config = Config::default();
+
+        if let Some(year) = values.integer("year")? {
+            config.year = Some(
+                u16::try_from(year)
+                    .ok()
+                    .filter(|year| *year >= 2015)
+                    .ok_or(format!("expected `year` to be 2015 or later, got {year}."))?,
+            );
+        }
+
+        let paths = &mut config.paths;
+        if let Some(data) = values.string("paths.data")? {
+            paths.data = data.into();
+        }
+        paths.inputs = values.string("paths.inputs")?.map(PathBuf::from);
+        paths.examples = values.string("paths.examples")?.map(PathBuf::from);
+        paths.puzzles = values.string("paths.puzzles")?.map(PathBuf::from);
+
+        let benchmark = &mut config.benchmark;
+        if let Some(budget) = values.positive("benchmark.budget_ms")? {
+            benchmark.budget = Duration::from_millis(budget);
+        }
+        if let Some(min_samples) = values.positive("benchmark.min_samples")? {
+            benchmark.min_samples = min_samples.into();
+        }
+        if let Some(max_samples) = values.positive("benchmark.max_samples")? {
+            benchmark.max_samples = max_samples.into();
+        }
+        if benchmark.min_samples > benchmark.max_samples {
+            return Err(format!(
+                "expected `benchmark.min_samples` ({}) to be at most `benchmark.max_samples` ({}).",
+                benchmark.min_samples, benchmark.max_samples
+            ));
+        }
+
+        config.run.timeout = values
+            .positive("run.timeout_secs")?
+            .map(Duration::from_secs);
+
+        if let Some(template) = values.string("scaffold.template")? {
+            config.scaffold.template = template
+                .parse()
+                .map_err(|e| format!("scaffold.template: {e}"))?;
+        }
+
+        let readme = &mut config.readme;
+        if let Some(columns) = values.string_array("readme.columns")? {
+            readme.columns = columns
+                .iter()
+                .map(|column| column.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("readme.columns: {e}"))?;
+        }
+        if let Some(sort) = values.string("readme.sort")? {
+            readme.sort = sort.parse().map_err(|e| format!("readme.sort: {e}"))?;
+        }
+        if let Some(highlight_slowest) = values.integer("readme.highlight_slowest")? {
+            readme.highlight_slowest = usize::try_from(highlight_slowest)
+                .map_err(|_| "expected `readme.highlight_slowest` to be positive.".to_string())?;
+        }
+        if let Some(day_link) = values.string("readme.day_link")? {
+            readme.day_link = day_link;
+        }
+        if let Some(total_unit) = values.string("readme.total_unit")? {
+            readme.total_unit = total_unit
+                .parse()
+                .map_err(|e| format!("readme.total_unit: {e}"))?;
+        }
+
+        if let Some(key) = values.0.keys().min() {
+            return Err(format!("unknown key `{key}`."));
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parsed values that are removed once read, so that unknown keys remain.
+pub(crate) struct Values(pub HashMap<String, Value>);
+
+impl Values {
+    pub(crate) fn string(&mut self, key: &str) -> Result<Option<String>, String> {
+        match self.0.remove(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(_) => Err(format!("expected `{key}` to be a string.")),
+        }
+    }
+
+    fn string_array(&mut self, key: &str) -> Result<Option<Vec<String>>, String> {
+        let error = || format!("expected `{key}` to be an array of strings.");
+        match self.0.remove(key) {
+            None => Ok(None),
+            Some(Value::Array(values)) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::String(s) => Ok(s),
+                    _ => Err(error()),
+                })
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(_) => Err(error()),
+        }
+    }
+
+    pub(crate) fn integer(&mut self, key: &str) -> Result<Option<i64>, String> {
+        match self.0.remove(key) {
+            None => Ok(None),
+            Some(Value::Integer(n)) => Ok(Some(n)),
+            Some(_) => Err(format!("expected `{key}` to be an integer.")),
+        }
+    }
+
+    /// A string or an integer, returned as string. Used for puzzle answers.
+    pub(crate) fn string_or_integer(&mut self, key: &str) -> Result<Option<String>, String> {
+        match self.0.remove(key) {
+            None => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(Value::Integer(n)) => Ok(Some(n.to_string())),
+            Some(_) => Err(format!("expected `{key}` to be a string or an integer.")),
+        }
+    }
+
+    /// An integer greater than zero.
+    fn positive(&mut self, key: &str) -> Result<Option<u64>, String> {
+        self.integer(key)?
+            .map(|n| {
+                u64::try_from(n).ok().filter(|n| *n > 0).ok_or(format!(
+                    "expected `{key}` to be greater than zero, got {n}."
+                ))
+            })
+            .transpose()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{Column, Config, Sort, Template, TotalUnit};
+    use crate::day;
+
+    #[test]
+    fn parses_empty_config() {
+        let config: Config = "# nothing configured\n".parse().unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn parses_readme_config() {
+        let config: Config = r#"
+            [readme]
+            columns = ["title", "part_1", "part_2", "verified", ] # comment
+            sort = "duration"
+            highlight_slowest = 3
+            day_link = "https://example.com/#{day}"
+            total_unit = "s"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            config.readme.columns,
+            [
+                Column::Title,
+                Column::Part1,
+                Column::Part2,
+                Column::Verified
+            ]
+        );
+        assert_eq!(config.readme.sort, Sort::Duration);
+        assert_eq!(config.readme.highlight_slowest, 3);
+        assert_eq!(config.readme.day_link, "https://example.com/#{day}");
+        assert_eq!(config.readme.total_unit, TotalUnit::Seconds);
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let error = "[readme]\ncolumns = [\"speed\"]"
+            .parse::<Config>()
+            .unwrap_err();
+        assert_eq!(error.contains("unknown column `speed`"), true);
+
+        let error = "[readme]\nhighlight_slowest = -1"
+            .parse::<Config>()
+            .unwrap_err();
+        assert_eq!(error.contains("positive"), true);
+
+        let error = "[benchmark]\nbudget_ms = 0".parse::<Config>().unwrap_err();
+        assert_eq!(
+            error,
+            "expected `benchmark.budget_ms` to be greater than zero, got 0."
+        );
+
+        let error = "[benchmark]\nmin_samples = 100\nmax_samples = 10"
+            .parse::<Config>()
+            .unwrap_err();
+        assert_eq!(error.contains("at most"), true);
+
+        let error = "year = 2012".parse::<Config>().unwrap_err();
+        assert_eq!(error, "expected `year` to be 2015 or later, got 2012.");
+
+        let error = "[paths]\ndata = 1".parse::<Config>().unwrap_err();
+        assert_eq!(error, "expected `paths.data` to be a string.");
+
+        let error = "[readme]\nsort = day".parse::<Config>().unwrap_err();
+        assert_eq!(error, "line 2: invalid value.");
+    }
+
+    #[test]
+    fn parses_project_config() {
+        let config: Config = r#"
+            year = 2023
+
+            [paths]
+            data = "aoc-data"
+            inputs = "private/inputs"
+
+            [benchmark]
+            budget_ms = 250
+            max_samples = 1_000
+
+            [run]
+            timeout_secs = 30
+
+            [scaffold]
+            template = "solution"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.year(), Some(2023));
+        assert_eq!(
+            config.paths.input(day!(1)),
+            PathBuf::from("private/inputs/01.txt")
+        );
+        assert_eq!(
+            config.paths.example(day!(1)),
+            PathBuf::from("aoc-data/examples/01.txt")
+        );
+        assert_eq!(
+            config.paths.data_file("timings.json"),
+            PathBuf::from("aoc-data/timings.json")
+        );
+        assert_eq!(config.benchmark.budget, Duration::from_millis(250));
+        assert_eq!(config.benchmark.min_samples, 10);
+        assert_eq!(config.benchmark.max_samples, 1000);
+        assert_eq!(config.run.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.scaffold.template, Template::Solution);
+    }
+
+    #[test]
+    fn parses_template_files() {
+        let config: Config = "[scaffold]\ntemplate = \"templates/day.txt\""
+            .parse()
+            .unwrap();
+        assert_eq!(
+            config.scaffold.template,
+            Template::File("templates/day.txt".into())
+        );
+        assert_eq!(
+            "[scaffold]\ntemplate = \"fancy\""
+                .parse::<Config>()
+                .is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let error = "[readme]\ncolour = \"red\"".parse::<Config>().unwrap_err();
+        assert_eq!(error, "unknown key `readme.colour`.");
+    }
+}
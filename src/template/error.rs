@@ -0,0 +1,110 @@
+/// Error type shared by the template commands. `main` prints it and exits with [`Error::exit_code`].
+use std::{
+    fmt::Display,
+    io,
+    process::{ExitStatus, Output},
+};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::readme_benchmarks;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command line arguments, the message includes the usage of the command.
+    Usage(String),
+    /// aoc-cli is missing or failed.
+    AocCli(AocCommandError),
+    /// A child process, e.g. `cargo run` for a solution, exited with a non-zero status.
+    ChildFailed { command: String, status: ExitStatus },
+    /// An I/O error, together with what was being done when it happened.
+    Io { context: String, source: io::Error },
+    /// Any other failure, e.g. a missing baseline or a readme without table marker.
+    Other(String),
+}
+
+impl Error {
+    /// Wraps an I/O error with a description of the failed operation.
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Error::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Exit code for the process: `2` for usage errors, the status of a failed child process,
+    /// `1` otherwise.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::ChildFailed { status, .. } => status.code().unwrap_or(1),
+            Error::AocCli(AocCommandError::BadExitStatus(Output { status, .. })) => {
+                status.code().unwrap_or(1)
+            }
+            _ => 1,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) | Error::Other(message) => write!(f, "{message}"),
+            Error::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::ChildFailed { command, status } => match status.code() {
+                Some(code) => write!(f, "`{command}` exited with status {code}."),
+                None => write!(f, "`{command}` was terminated by a signal."),
+            },
+            Error::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(message) => Error::Other(message),
+            readme_benchmarks::Error::IO(source) => Error::io("failed to update README.md", source),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    #[test]
+    fn maps_errors_to_exit_codes() {
+        use super::{AocCommandError, Error};
+
+        assert_eq!(Error::Usage("bad flag".into()).exit_code(), 2);
+        assert_eq!(Error::Other("no timings".into()).exit_code(), 1);
+        assert_eq!(
+            Error::AocCli(AocCommandError::CommandNotFound).exit_code(),
+            1
+        );
+    }
+
+    #[test]
+    fn displays_context() {
+        use super::{io, Error};
+
+        let err = Error::io(
+            "Failed to create input file",
+            io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+        );
+        assert_eq!(err.to_string(), "Failed to create input file: denied");
+    }
+}
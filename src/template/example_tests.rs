@@ -0,0 +1,199 @@
+/// Returns the name of the `Solution` type passed to `solution!`, if the day uses one.
+fn solution_type(source: &str) -> Option<&str> {
+    let start = source.find("solution!(")? + "solution!(".len();
+    let end = start + source[start..].find(')')?;
+    let solution = source[start..end].split(',').nth(1)?.trim();
+
+    let is_ident = solution.starts_with(|c: char| c.is_ascii_alphabetic())
+        && solution
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    (is_ident && solution != "solve").then_some(solution)
+}
+
+/// Turns `--expect` into the expected result, numbers are compared as numbers and everything else as string.
+fn expected_literal(expected: Option<&str>) -> String {
+    match expected {
+        None => "None".into(),
+        Some(value) if value.parse::<i128>().is_ok() => format!("Some({value})"),
+        Some(value) => format!("Some({value:?}.to_string())"),
+    }
+}
+
+#[must_use]
+pub fn test_name(part: u8, example: u8) -> String {
+    let part = if part == 1 { "one" } else { "two" };
+    format!("test_part_{part}_example_{example}")
+}
+
+/// A test of `part` against the example file `NN-{example}.txt`, in the style of the solution in `source`.
+#[must_use]
+pub fn example_test(source: &str, part: u8, example: u8, expected: Option<&str>) -> String {
+    let func = if part == 1 { "part_one" } else { "part_two" };
+    let read = format!("&advent_of_code::template::read_file_part(\"examples\", DAY, {example})");
+
+    let body = match solution_type(source) {
+        Some(solution) => format!(
+            "        let parsed = {solution}::parse({read});\n        let result = {solution}::{func}(&parsed);\n"
+        ),
+        None => format!("        let result = {func}({read});\n"),
+    };
+
+    format!(
+        "    #[test]\n    fn {}() {{\n{body}        assert_eq!(result, {});\n    }}\n",
+        test_name(part, example),
+        expected_literal(expected)
+    )
+}
+
+/// Finds the closing brace of the block opened at `open`, skipping braces in strings, chars and comments.
+fn closing_brace(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'\'' if bytes.get(i + 1) == Some(&b'\\') => {
+                i += 2;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += 1;
+                }
+            }
+            b'\'' if bytes.get(i + 2) == Some(&b'\'') => i += 2,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Adds `test` at the end of `mod tests`, or creates the module if the solution has none.
+#[must_use]
+pub fn insert_test(source: &str, test: &str) -> String {
+    let tests_block = source
+        .find("mod tests {")
+        .map(|pos| pos + "mod tests ".len())
+        .and_then(|open| closing_brace(source, open));
+
+    match tests_block {
+        Some(close) => format!(
+            "{}\n\n{test}{}",
+            source[..close].trim_end(),
+            &source[close..]
+        ),
+        None => format!(
+            "{}\n\n#[cfg(test)]\nmod tests {{\n    use super::*;\n\n{test}}}\n",
+            source.trim_end()
+        ),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{example_test, insert_test, solution_type};
+
+    const FUNCTIONS: &str = "advent_of_code::solution!(1);
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let _ = format!(\"{input}}\");
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));
+        assert_eq!(result, None);
+    }
+}
+";
+
+    #[test]
+    fn detects_solution_types() {
+        assert_eq!(solution_type(FUNCTIONS), None);
+        assert_eq!(
+            solution_type("advent_of_code::solution!(6, Day06);"),
+            Some("Day06")
+        );
+        assert_eq!(
+            solution_type("advent_of_code::solution!(6, Day06, 1);"),
+            Some("Day06")
+        );
+        assert_eq!(solution_type("advent_of_code::solution!(6, solve);"), None);
+        assert_eq!(solution_type("advent_of_code::solution!(6, 2);"), None);
+    }
+
+    #[test]
+    fn renders_tests() {
+        let test = example_test(FUNCTIONS, 2, 3, Some("42"));
+        assert_eq!(test.contains("fn test_part_two_example_3() {"), true);
+        assert_eq!(
+            test.contains(
+                "part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 3));"
+            ),
+            true
+        );
+        assert_eq!(test.contains("assert_eq!(result, Some(42));"), true);
+
+        let test = example_test("advent_of_code::solution!(6, Day06);", 1, 2, Some("abc"));
+        assert_eq!(
+            test.contains("let result = Day06::part_one(&parsed);"),
+            true
+        );
+        assert_eq!(test.contains("Some(\"abc\".to_string())"), true);
+    }
+
+    #[test]
+    fn inserts_into_tests_module() {
+        let source = insert_test(FUNCTIONS, &example_test(FUNCTIONS, 1, 2, None));
+        assert_eq!(source.matches("mod tests {").count(), 1);
+        assert_eq!(
+            source.ends_with(
+                "        assert_eq!(result, None);\n    }\n\n    #[test]\n    fn test_part_one_example_2() {\n        let result = part_one(&advent_of_code::template::read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, None);\n    }\n}\n"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn creates_tests_module() {
+        let source = insert_test(
+            "advent_of_code::solution!(1);\n",
+            &example_test("", 1, 2, None),
+        );
+        assert_eq!(
+            source.starts_with("advent_of_code::solution!(1);\n\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n"),
+            true
+        );
+        assert_eq!(source.ends_with("    }\n}\n"), true);
+    }
+}
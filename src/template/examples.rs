@@ -0,0 +1,326 @@
+/// Example manifests like `data/examples/01.toml`, which list example files with their expected answers.
+/// [`crate::example_tests!`] generates a test for every listed example.
+use std::{env, fs, io, io::ErrorKind, str::FromStr};
+
+use crate::template::config::{self, Values};
+use crate::template::params::{self, Override};
+use crate::template::toml::parse_toml;
+use crate::template::Day;
+
+/// An example file and the answers it is expected to produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name inside the examples folder, e.g. `01-2.txt`.
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters that differ from the real input, e.g. `params.width = 11`.
+    pub params: Vec<Override>,
+}
+
+impl Example {
+    #[must_use]
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    /// The example as `[[example]]` table of a manifest.
+    fn to_toml(&self) -> Result<String, String> {
+        let mut s = format!("[[example]]\nfile = {}\n", toml_string(&self.file)?);
+        for part in 1..=2 {
+            if let Some(expected) = self.expected(part) {
+                let value = toml_value(expected)?;
+                s.push_str(&format!("part_{part} = {value}\n"));
+            }
+        }
+        for (name, value) in &self.params {
+            s.push_str(&format!("params.{name} = {}\n", toml_value(value)?));
+        }
+        Ok(s)
+    }
+}
+
+/// An integer as TOML integer, anything else as TOML string.
+fn toml_value(s: &str) -> Result<String, String> {
+    if s.parse::<i64>().is_ok() {
+        Ok(s.to_string())
+    } else {
+        toml_string(s)
+    }
+}
+
+fn toml_string(s: &str) -> Result<String, String> {
+    if s.contains('"') || s.contains('\n') {
+        Err(format!("`{s}` can not be stored in an example manifest."))
+    } else {
+        Ok(format!("\"{s}\""))
+    }
+}
+
+/// Whether the tests of a solution are generated from its manifest with [`crate::example_tests!`].
+#[must_use]
+pub fn uses_manifest(source: &str) -> bool {
+    source.contains("example_tests!(")
+}
+
+/// The examples of a day, in the order of the manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    /// Reads the manifest of a day. If not present, returns an empty manifest.
+    pub fn read(day: Day) -> Result<Self, String> {
+        let path = config::get().paths.example_manifest(day);
+        match fs::read_to_string(&path) {
+            Ok(s) => s
+                .parse()
+                .map_err(|e| format!("invalid {}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(format!("could not read {}: {e}", path.display())),
+        }
+    }
+
+    /// A manifest listing example files without expected answers, as created by `cargo scaffold`.
+    #[must_use]
+    pub fn with_files(files: impl IntoIterator<Item = String>) -> Self {
+        Manifest {
+            examples: files
+                .into_iter()
+                .map(|file| Example {
+                    file,
+                    part_1: None,
+                    part_2: None,
+                    params: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    /// The manifest as TOML, with a comment explaining the format.
+    pub fn to_toml(&self) -> Result<String, String> {
+        let tables = self
+            .examples
+            .iter()
+            .map(Example::to_toml)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!(
+            "# Every example becomes a test. Add the expected answers, e.g. `part_1 = 42`.\n\n{}",
+            tables.join("\n")
+        ))
+    }
+
+    /// Appends an example to the manifest of a day, keeping comments and formatting of the file.
+    pub fn append(day: Day, example: &Example) -> Result<(), io::Error> {
+        let path = config::get().paths.example_manifest(day);
+        let table = example
+            .to_toml()
+            .map_err(|e| io::Error::new(ErrorKind::InvalidInput, e))?;
+
+        let content = match fs::read_to_string(&path) {
+            Ok(s) if s.trim().is_empty() => table,
+            Ok(s) => format!("{}\n\n{table}", s.trim_end()),
+            Err(e) if e.kind() == ErrorKind::NotFound => table,
+            Err(e) => return Err(e),
+        };
+
+        fs::write(path, content)
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = Values(parse_toml(s)?);
+        let mut examples = vec![];
+
+        while values
+            .0
+            .keys()
+            .any(|key| key.starts_with(&format!("example.{}.", examples.len())))
+        {
+            let key = |name: &str| format!("example.{}.{name}", examples.len());
+
+            let mut names: Vec<String> = values
+                .0
+                .keys()
+                .filter_map(|k| k.strip_prefix(&key("params.")).map(ToString::to_string))
+                .collect();
+            names.sort();
+            let params = names
+                .into_iter()
+                .map(|name| {
+                    let value = values
+                        .string_or_integer(&key(&format!("params.{name}")))?
+                        .unwrap_or_default();
+                    Ok((name, value))
+                })
+                .collect::<Result<_, String>>()?;
+
+            examples.push(Example {
+                file: values
+                    .string(&key("file"))?
+                    .ok_or(format!("expected `{}` to be set.", key("file")))?,
+                part_1: values.string_or_integer(&key("part_1"))?,
+                part_2: values.string_or_integer(&key("part_2"))?,
+                params,
+            });
+        }
+
+        if let Some(key) = values.0.keys().min() {
+            return Err(format!("unknown key `{key}`."));
+        }
+
+        Ok(Manifest { examples })
+    }
+}
+
+/// Runs `part` of the example at `index` of the manifest of `day` with the parameters of the example,
+/// and panics if its answer does not match. Without a part, only checks that the example can be read.
+///
+/// # Panics
+///
+/// If the manifest is invalid, the example file is missing, it sets a parameter that is not one of
+/// `declared`, it has no answer for `part` or the answer does not match.
+pub fn check(
+    day: Day,
+    index: usize,
+    part: Option<u8>,
+    declared: &[&str],
+    solve_part: impl Fn(&str, u8) -> Option<String>,
+) {
+    let manifest = Manifest::read(day).unwrap_or_else(|e| panic!("{e}"));
+    let example = manifest
+        .examples
+        .get(index)
+        .unwrap_or_else(|| panic!("the manifest of day {day} has no example {}.", index + 1));
+
+    let path = env::current_dir()
+        .unwrap()
+        .join(config::get().paths.folder("examples"))
+        .join(&example.file);
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not read example {}: {e}", path.display()));
+
+    if let Some(name) = params::unknown(&example.params, declared).first() {
+        panic!(
+            "{} sets the parameter `{name}`, which day {day} does not declare with `params!`.",
+            example.file
+        );
+    }
+
+    let Some(part) = part else {
+        return;
+    };
+    let expected = example
+        .expected(part)
+        .unwrap_or_else(|| panic!("{} has no expected answer for part {part}.", example.file));
+
+    let result = params::with_overrides(&example.params, || solve_part(&input, part));
+    assert!(
+        result.as_deref() == Some(expected),
+        "{} part {part}: expected `{expected}`, got `{}`",
+        example.file,
+        result.as_deref().unwrap_or("None")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Manifest};
+
+    #[test]
+    fn parses_manifests() {
+        let manifest: Manifest = r#"
+            # examples of day 1
+            [[example]]
+            file = "01.txt"
+            part_1 = 11
+            part_2 = 31
+
+            [[example]]
+            file = "01-2.txt"
+            part_2 = "abc"
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            manifest.examples,
+            vec![
+                Example {
+                    file: "01.txt".into(),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                    params: vec![],
+                },
+                Example {
+                    file: "01-2.txt".into(),
+                    part_1: None,
+                    part_2: Some("abc".into()),
+                    params: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_params() {
+        let toml =
+            "[[example]]\nfile = \"14.txt\"\npart_1 = 12\nparams.width = 11\nparams.height = 7\n";
+        let manifest: Manifest = toml.parse().unwrap();
+        assert_eq!(
+            manifest.examples[0].params,
+            vec![("height".into(), "7".into()), ("width".into(), "11".into())]
+        );
+        assert_eq!(
+            manifest.examples[0].to_toml().unwrap().parse::<Manifest>(),
+            Ok(manifest)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_keys() {
+        "[[example]]\nfile = \"01.txt\"\npart_3 = 1"
+            .parse::<Manifest>()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_missing_files() {
+        "[[example]]\npart_1 = 1".parse::<Manifest>().unwrap();
+    }
+
+    #[test]
+    fn creates_manifests() {
+        let manifest = Manifest::with_files(["01.txt".to_string(), "01-2.txt".to_string()]);
+        let toml = manifest.to_toml().unwrap();
+        assert_eq!(toml.starts_with("# Every example becomes a test."), true);
+        assert_eq!(toml.parse::<Manifest>().unwrap(), manifest);
+    }
+
+    #[test]
+    fn serializes_examples() {
+        let example = Example {
+            file: "01-2.txt".into(),
+            part_1: None,
+            part_2: Some("42".into()),
+            params: vec![],
+        };
+        let toml = example.to_toml().unwrap();
+        assert_eq!(toml, "[[example]]\nfile = \"01-2.txt\"\npart_2 = 42\n");
+
+        let manifest: Manifest = toml.parse().unwrap();
+        assert_eq!(manifest.examples, vec![example]);
+    }
+}
@@ -0,0 +1,446 @@
+/// Mutation-based fuzzing of the parse path of a solution, without external tools.
+///
+/// `cargo fuzz <day>` feeds mutated versions of the example files into the function registered with
+/// [`fuzz_target!`](crate::fuzz_target), or into both parts if a day does not register one. Panics are
+/// minimized and saved as reproducers in `data/fuzz/NN/`, which are re-run first on the next invocation.
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{env, process, thread};
+
+use crate::differential::Rng;
+use crate::template::examples::Manifest;
+use crate::template::{config, log, Day};
+
+/// The parse path of a solution, called with every mutated input.
+pub type Target = fn(&str);
+
+/// Fallback for days without [`fuzz_target!`](crate::fuzz_target). `solution!` glob-imports this module,
+/// so the `FUZZ_TARGET` defined by `fuzz_target!` takes precedence.
+pub mod fallback {
+    pub const FUZZ_TARGET: Option<super::Target> = None;
+}
+
+/// Inputs running longer than this are reported as hangs.
+const HANG_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Characters that often trip up parsers, in addition to the characters of the input itself.
+const INTERESTING_CHARS: &[char] = &[
+    '0', '9', '-', '+', ',', '|', ':', ' ', '\n', '\t', '\0', '#', '.', '@', '^', 'a', 'Z', 'é',
+    '\u{feff}',
+];
+
+/// Numbers that often overflow or underflow, used to replace numbers of the input.
+const INTERESTING_NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "256",
+    "4294967296",
+    "18446744073709551616",
+    "99999999999999999999999",
+];
+
+/// Settings passed from `cargo fuzz` to a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl Settings {
+    /// The arguments that pass these settings to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--fuzz".into(),
+            "--iterations".into(),
+            self.iterations.to_string(),
+            "--seed".into(),
+            self.seed.to_string(),
+        ]
+    }
+
+    fn from_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|arg| arg == "--fuzz") {
+            return None;
+        }
+
+        let value = |flag: &str| {
+            let index = args.iter().position(|arg| arg == flag)?;
+            args.get(index + 1)
+        };
+
+        Some(Settings {
+            iterations: value("--iterations")
+                .and_then(|iterations| iterations.parse().ok())
+                .unwrap_or_default(),
+            seed: value("--seed")
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// A seed that differs between invocations, for runs without `--seed`.
+#[must_use]
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Reads the settings of `cargo fuzz` from the command-line arguments of a solution.
+#[must_use]
+pub fn requested() -> Option<Settings> {
+    Settings::from_args(&env::args().collect::<Vec<_>>())
+}
+
+/// A panic caught while running the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Panic {
+    /// Where the panic happened, e.g. `src/bin/15.rs:488:55`.
+    location: String,
+    message: String,
+}
+
+static LAST_PANIC: Mutex<Option<Panic>> = Mutex::new(None);
+
+/// Runs the target on `input` and returns the panic it caused, if any.
+fn run_target(target: Target, input: &str) -> Option<Panic> {
+    match panic::catch_unwind(AssertUnwindSafe(|| target(input))) {
+        Ok(()) => None,
+        Err(_) => Some(LAST_PANIC.lock().unwrap().take().unwrap_or(Panic {
+            location: "unknown location".into(),
+            message: String::new(),
+        })),
+    }
+}
+
+/// Re-runs the saved reproducers, then fuzzes the target with mutated examples and saves a minimized
+/// reproducer for every panic location it finds. Exits with status 1 if any input panics or hangs.
+pub fn run(day: Day, target: Target, settings: &Settings) {
+    let _quiet = log::suppress();
+    let folder = config::get().paths.fuzz(day);
+
+    // NOTE: panics are expected, record them instead of printing a message for each of them.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        let location = info
+            .location()
+            .map_or("unknown location".into(), ToString::to_string);
+        *LAST_PANIC.lock().unwrap() = Some(Panic { location, message });
+    }));
+
+    let current = Arc::new(Mutex::new(None));
+    watch_for_hangs(Arc::clone(&current), folder.clone());
+
+    let run_watched = |input: &str| {
+        *current.lock().unwrap() = Some((Instant::now(), input.to_string()));
+        let result = run_target(target, input);
+        *current.lock().unwrap() = None;
+        result
+    };
+
+    let mut found: Vec<Panic> = vec![];
+
+    for (path, input) in read_reproducers(&folder) {
+        match run_watched(&input) {
+            Some(panic) => {
+                println!("{} still panics: {}", path.display(), describe(&panic));
+                found.push(panic);
+            }
+            None => println!("{} does not panic anymore.", path.display()),
+        }
+    }
+
+    let corpus = read_corpus(day);
+    let mut rng = Rng::new(settings.seed);
+
+    println!(
+        "Fuzzing day {day} with {} inputs, seed {}",
+        settings.iterations, settings.seed
+    );
+
+    for _ in 0..settings.iterations {
+        let original = &corpus[rng.below(corpus.len() as u64) as usize];
+        let input = mutate(&mut rng, original);
+        let Some(panic) = run_watched(&input) else {
+            continue;
+        };
+        if found.iter().any(|known| known.location == panic.location) {
+            continue;
+        }
+
+        let reproducer = minimize(&input, |candidate| {
+            run_watched(candidate).is_some_and(|p| p.location == panic.location)
+        });
+        let path = folder.join(format!("panic-{}.txt", file_name(&panic.location)));
+        match save(&path, &reproducer) {
+            Ok(()) => println!("{} → {}", describe(&panic), path.display()),
+            Err(e) => println!("{} (could not save reproducer: {e})", describe(&panic)),
+        }
+        found.push(panic);
+    }
+
+    panic::set_hook(default_hook);
+
+    if found.is_empty() {
+        println!("No panics found.");
+    } else {
+        println!("Found {} panic locations.", found.len());
+        process::exit(1);
+    }
+}
+
+fn describe(panic: &Panic) -> String {
+    format!("panicked at {}: {}", panic.location, panic.message)
+}
+
+/// Turns a panic location into a file name, e.g. `src/bin/15.rs:488:55` into `15-488-55`.
+fn file_name(location: &str) -> String {
+    let location = location.rsplit('/').next().unwrap_or(location);
+    location
+        .replace(".rs", "")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn save(path: &Path, input: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, input)
+}
+
+/// Exits once an input runs longer than [`HANG_TIMEOUT`], saving it as `hang.txt`.
+fn watch_for_hangs(current: Arc<Mutex<Option<(Instant, String)>>>, folder: PathBuf) {
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_millis(100));
+        let current = current.lock().unwrap();
+        if let Some((started, input)) = current.as_ref() {
+            if started.elapsed() > HANG_TIMEOUT {
+                let path = folder.join("hang.txt");
+                match save(&path, input) {
+                    Ok(()) => println!(
+                        "Input did not finish within {HANG_TIMEOUT:?} → {}",
+                        path.display()
+                    ),
+                    Err(e) => println!(
+                        "Input did not finish within {HANG_TIMEOUT:?} (could not save it: {e})"
+                    ),
+                }
+                process::exit(1);
+            }
+        }
+    });
+}
+
+/// The saved reproducers of a day, sorted by file name.
+fn read_reproducers(folder: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+    let mut reproducers: Vec<(PathBuf, String)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let input = fs::read_to_string(&path).ok()?;
+            Some((path, input))
+        })
+        .collect();
+    reproducers.sort();
+    reproducers
+}
+
+/// The example files of a day that mutations start from.
+fn read_corpus(day: Day) -> Vec<String> {
+    let examples = config::get().paths.folder("examples");
+    let files = match Manifest::read(day) {
+        Ok(manifest) if !manifest.examples.is_empty() => manifest
+            .examples
+            .into_iter()
+            .map(|example| examples.join(example.file))
+            .collect(),
+        _ => vec![config::get().paths.example(day)],
+    };
+
+    let corpus: Vec<String> = files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+
+    if corpus.is_empty() {
+        vec![String::new()]
+    } else {
+        corpus
+    }
+}
+
+/// Applies one to four random mutations to `input`.
+fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.between(1, 4) {
+        mutate_once(rng, &mut chars);
+    }
+    chars.into_iter().collect()
+}
+
+fn mutate_once(rng: &mut Rng, chars: &mut Vec<char>) {
+    let len = chars.len() as u64;
+    let position = rng.below(len + 1) as usize;
+
+    let interesting_char = |rng: &mut Rng, chars: &[char]| {
+        if chars.is_empty() || rng.one_in(2) {
+            INTERESTING_CHARS[rng.below(INTERESTING_CHARS.len() as u64) as usize]
+        } else {
+            chars[rng.below(chars.len() as u64) as usize]
+        }
+    };
+
+    match rng.below(8) {
+        0 if position < chars.len() => chars[position] = interesting_char(rng, chars),
+        1 => {
+            let c = interesting_char(rng, chars);
+            chars.insert(position, c);
+        }
+        2 => {
+            let end = (position + rng.between(1, 8) as usize).min(chars.len());
+            chars.drain(position..end);
+        }
+        3 => {
+            let end = (position + rng.between(1, 8) as usize).min(chars.len());
+            let copy: Vec<char> = chars[position..end].to_vec();
+            chars.splice(position..position, copy);
+        }
+        4 => chars.truncate(position),
+        5 => {
+            let numbers = number_ranges(chars);
+            if !numbers.is_empty() {
+                let (start, end) = numbers[rng.below(numbers.len() as u64) as usize];
+                let number =
+                    INTERESTING_NUMBERS[rng.below(INTERESTING_NUMBERS.len() as u64) as usize];
+                chars.splice(start..end, number.chars());
+            }
+        }
+        _ => {
+            let mut lines: Vec<Vec<char>> =
+                chars.split(|c| *c == '\n').map(<[char]>::to_vec).collect();
+            let line = rng.below(lines.len() as u64) as usize;
+            match rng.below(3) {
+                0 => {
+                    lines.remove(line);
+                }
+                1 => lines.insert(line, lines[line].clone()),
+                _ => {
+                    let other = rng.below(lines.len() as u64) as usize;
+                    lines.swap(line, other);
+                }
+            }
+            *chars = lines.join(&'\n');
+        }
+    }
+}
+
+/// Start and end of every run of digits.
+fn number_ranges(chars: &[char]) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut start = None;
+    for (i, c) in chars.iter().chain([&' ']).enumerate() {
+        match (c.is_ascii_digit(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                ranges.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Removes chunks of decreasing size from `input` as long as `still_fails` holds.
+fn minimize(input: &str, still_fails: impl Fn(&str) -> bool) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut chunk = chars.len().div_ceil(2);
+
+    while chunk > 0 {
+        let mut start = 0;
+        while start < chars.len() {
+            let end = (start + chunk).min(chars.len());
+            let candidate: String = chars[..start].iter().chain(&chars[end..]).collect();
+            if still_fails(&candidate) {
+                chars = candidate.chars().collect();
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    chars.into_iter().collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{file_name, minimize, mutate, number_ranges, Settings};
+    use crate::differential::Rng;
+
+    #[test]
+    fn minimizes_failing_inputs() {
+        let reproducer = minimize("47|53\n97|13\n97|x1\n75|29", |input| input.contains('x'));
+        assert_eq!(reproducer, "x");
+
+        let reproducer = minimize("1,2,3,40,5", |input| input.contains("40"));
+        assert_eq!(reproducer, "40");
+    }
+
+    #[test]
+    fn mutates_reproducibly() {
+        let (mut a, mut b) = (Rng::new(3), Rng::new(3));
+        for _ in 0..100 {
+            assert_eq!(
+                mutate(&mut a, "47|53\n97|13"),
+                mutate(&mut b, "47|53\n97|13")
+            );
+        }
+        let mut rng = Rng::new(3);
+        assert_eq!(
+            (0..100).any(|_| mutate(&mut rng, "47|53\n97|13") != "47|53\n97|13"),
+            true
+        );
+    }
+
+    #[test]
+    fn finds_numbers() {
+        let chars: Vec<char> = "47|53\n9".chars().collect();
+        assert_eq!(number_ranges(&chars), vec![(0, 2), (3, 5), (6, 7)]);
+    }
+
+    #[test]
+    fn names_reproducers_after_locations() {
+        assert_eq!(file_name("src/bin/15.rs:488:55"), "15-488-55");
+    }
+
+    #[test]
+    fn passes_settings_as_arguments() {
+        let settings = Settings {
+            iterations: 100,
+            seed: 7,
+        };
+        let mut args = vec!["target/debug/05".to_string()];
+        args.extend(settings.to_args());
+        assert_eq!(Settings::from_args(&args), Some(settings));
+        assert_eq!(Settings::from_args(&args[..1]), None);
+    }
+}
@@ -0,0 +1,205 @@
+/// Debug logging for solutions.
+///
+/// Messages are written to stderr so they don't interfere with the timing output on stdout.
+/// The [`aoc_debug!`](crate::aoc_debug), [`aoc_trace!`](crate::aoc_trace) and
+/// [`aoc_indent!`](crate::aoc_indent) macros compile to nothing in release builds, so they can stay
+/// in a solution without affecting benchmarks.
+use std::env;
+use std::fmt::Arguments;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+use crate::template::{Day, ANSI_ITALIC, ANSI_RESET};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+static SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// Verbosity of a log message. `--verbose` enables debug messages, `--verbose --verbose` also trace messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug = 1,
+    Trace = 2,
+}
+
+/// Reads the verbosity from the command-line arguments passed to a solution.
+///
+/// Logging stays disabled if `--log-day` arguments are present and none of them matches `day`.
+pub fn init(day: Day) {
+    let args: Vec<String> = env::args().collect();
+    LEVEL.store(level_from_args(&args, day), Ordering::Relaxed);
+}
+
+fn level_from_args(args: &[String], day: Day) -> u8 {
+    let verbosity = args
+        .iter()
+        .map(|arg| match arg.as_str() {
+            "--verbose" => 1,
+            s if s.starts_with("-v") && s[1..].chars().all(|c| c == 'v') => s.len() - 1,
+            _ => 0,
+        })
+        .sum::<usize>();
+
+    let day_filter: Vec<&String> = args
+        .iter()
+        .zip(args.iter().skip(1))
+        .filter(|(flag, _)| *flag == "--log-day")
+        .map(|(_, value)| value)
+        .collect();
+
+    let is_day_selected = day_filter.is_empty()
+        || day_filter
+            .iter()
+            .any(|value| value.parse::<Day>().is_ok_and(|d| d == day));
+
+    if is_day_selected {
+        u8::try_from(verbosity).unwrap_or(u8::MAX)
+    } else {
+        0
+    }
+}
+
+/// Builds the arguments that enable logging in a solution, used to forward `--verbose` and `--log-day`.
+#[must_use]
+pub fn forward_args(verbosity: u8, days: &[Day]) -> Vec<String> {
+    let mut args = vec!["--verbose".to_string(); verbosity.into()];
+    for day in days {
+        args.push("--log-day".to_string());
+        args.push(day.to_string());
+    }
+    args
+}
+
+/// Whether messages of `level` are currently written.
+pub fn enabled(level: Level) -> bool {
+    !SUPPRESSED.load(Ordering::Relaxed) && LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Writes a message to stderr, indented by the current [`indent`] depth.
+pub fn write(level: Level, message: Arguments) {
+    let indent = "  ".repeat(DEPTH.load(Ordering::Relaxed));
+    let prefix = match level {
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    };
+    for line in message.to_string().lines() {
+        eprintln!("{ANSI_ITALIC}{prefix}{ANSI_RESET} {indent}{line}");
+    }
+}
+
+/// Indents all messages until the returned guard is dropped, e.g. for recursive calls.
+#[must_use]
+pub fn indent() -> Indent {
+    DEPTH.fetch_add(1, Ordering::Relaxed);
+    Indent
+}
+
+/// Guard returned by [`indent`].
+pub struct Indent;
+
+impl Drop for Indent {
+    fn drop(&mut self) {
+        DEPTH.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Silences all messages until the returned guard is dropped. Used while benchmarking.
+#[must_use]
+pub(crate) fn suppress() -> Suppress {
+    Suppress(SUPPRESSED.swap(true, Ordering::Relaxed))
+}
+
+/// Guard returned by [`suppress`].
+pub(crate) struct Suppress(bool);
+
+impl Drop for Suppress {
+    fn drop(&mut self) {
+        SUPPRESSED.store(self.0, Ordering::Relaxed);
+    }
+}
+
+/// Writes a debug message to stderr if the solution runs with `--verbose`.
+/// Compiles to nothing in release builds.
+///
+/// ```
+/// advent_of_code::aoc_debug!("visited {} fields", 42);
+/// ```
+#[macro_export]
+macro_rules! aoc_debug {
+    ($($arg:tt)*) => {
+        $crate::__aoc_log!($crate::template::log::Level::Debug, $($arg)*)
+    };
+}
+
+/// Writes a trace message to stderr if the solution runs with `--verbose --verbose`.
+/// Compiles to nothing in release builds.
+#[macro_export]
+macro_rules! aoc_trace {
+    ($($arg:tt)*) => {
+        $crate::__aoc_log!($crate::template::log::Level::Trace, $($arg)*)
+    };
+}
+
+/// Indents log messages until the end of the current scope. Compiles to nothing in release builds.
+///
+/// ```
+/// fn search(depth: u32) {
+///     advent_of_code::aoc_indent!();
+///     advent_of_code::aoc_trace!("depth {depth}");
+/// }
+/// ```
+#[macro_export]
+macro_rules! aoc_indent {
+    () => {
+        #[cfg(debug_assertions)]
+        let _aoc_indent = $crate::template::log::indent();
+    };
+}
+
+// Not part of the public API
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __aoc_log {
+    ($level:expr, $($arg:tt)*) => {{
+        // NOTE: `cfg` is evaluated in the crate calling the macro, i.e. the solution binary.
+        #[cfg(debug_assertions)]
+        if $crate::template::log::enabled($level) {
+            $crate::template::log::write($level, format_args!($($arg)*));
+        }
+        // keep arguments type-checked and "used" in release builds; the branch is optimized out.
+        #[cfg(not(debug_assertions))]
+        if false {
+            $crate::template::log::write($level, format_args!($($arg)*));
+        }
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::level_from_args;
+    use crate::day;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_verbosity() {
+        assert_eq!(level_from_args(&args("14"), day!(14)), 0);
+        assert_eq!(level_from_args(&args("14 --verbose"), day!(14)), 1);
+        assert_eq!(level_from_args(&args("14 -vv"), day!(14)), 2);
+        assert_eq!(level_from_args(&args("14 --verbose -v"), day!(14)), 2);
+        assert_eq!(level_from_args(&args("14 --submit 1"), day!(14)), 0);
+    }
+
+    #[test]
+    fn filters_days() {
+        assert_eq!(level_from_args(&args("-v --log-day 14"), day!(14)), 1);
+        assert_eq!(
+            level_from_args(&args("-v --log-day 3 --log-day 14"), day!(14)),
+            1
+        );
+        assert_eq!(level_from_args(&args("-v --log-day 3"), day!(14)), 0);
+    }
+}
@@ -1,25 +1,48 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod examples;
+pub mod fuzz;
+pub mod log;
+pub mod params;
+pub mod phase;
 pub mod runner;
+pub mod stress;
 
 pub use day::*;
+pub use error::Error;
+pub use solution::Solution;
 
+mod answers;
+mod baseline;
+mod benchmark_export;
+mod config;
 mod day;
+mod error;
+/// Generates tests for additional example files and inserts them into the `mod tests` of a solution.
+mod example_tests;
+mod progress;
 mod readme_benchmarks;
 mod run_multi;
+mod solution;
+mod submissions;
 mod timings;
+mod toml;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// The folders `inputs` and `examples` can be moved with `[paths]` in `aoc.toml`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -29,8 +52,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
+        .join(config::get().paths.folder(folder))
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
@@ -39,6 +61,17 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Pass the name of a type implementing [`Solution`](crate::template::Solution) after the day to parse the
+/// input once and share it between both parts, e.g. `solution!(6, Day06)` or `solution!(6, Day06, 1)`.
+///
+/// With `solve` as last parameter, both parts are computed by a single function returning both answers,
+/// e.g. `solution!(6, solve)` for a function `solve(input: &str)` or `solution!(6, Day06, solve)`.
+///
+/// Parts with alternative implementations list their variants, the default first, e.g.
+/// `solution!(11, part_one: [naive, memoized], part_two: [memoized])` for functions `naive` and `memoized`.
+/// Use `name => function` to name a variant differently from its function, e.g. `part_one: [naive => part_one]`.
+/// `cargo solve <day> --variant <name>` runs another variant and `cargo time <day> --variants` compares all of them.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +83,112 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, solve) => {
+        $crate::solution!(@impl_solve $day);
+    };
+    ($day:expr, $($part:ident: [$($variant:ident $(=> $func:path)?),+ $(,)?]),+ $(,)?) => {
+        $crate::solution!(@impl_variants $day, $( [$part, $($variant $(=> $func)?),+] )+);
+    };
+    ($day:expr, $solution:ident, solve) => {
+        $crate::solution!(@impl_parsed_solve $day, $solution);
+    };
+    ($day:expr, $solution:ident) => {
+        $crate::solution!(@impl_parsed $day, $solution, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, $solution:ident, 1) => {
+        $crate::solution!(@impl_parsed $day, $solution, [part_one, 1]);
+    };
+    ($day:expr, $solution:ident, 2) => {
+        $crate::solution!(@impl_parsed $day, $solution, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@main $day,
+            fuzz: |input| { $( std::hint::black_box($func(input)); )* },
+            stress: &[$(
+                (part_name($part), &|input| { std::hint::black_box($func(input)); }),
+            )*],
+            run: |input| { $( run_part($func, &input, DAY, $part); )* }
+        );
+    };
+
+    (@impl_solve $day:expr) => {
+        $crate::solution!(@main $day,
+            fuzz: |input| { std::hint::black_box(solve(input)); },
+            stress: &[
+                ("Solve", &|input| { std::hint::black_box(solve(input)); }),
+            ],
+            run: |input| { run_solve(solve, &input, DAY); }
+        );
+    };
+
+    (@impl_variants $day:expr, $( [$part:ident, $($variant:ident $(=> $func:path)?),+] )+) => {
+        $crate::solution!(@main $day,
+            check: check_variant(&[$($(stringify!($variant)),+),+]),
+            fuzz: |input| {$(
+                let variants = $crate::variants![
+                    $($variant => $crate::solution!(@variant_fn $variant $(=> $func)?)),+
+                ];
+                for variant in &variants {
+                    std::hint::black_box((variant.run)(input));
+                }
+            )+},
+            stress: &[$(
+                (part_name($crate::solution!(@part_number $part)), &|input| {
+                    let variants = $crate::variants![
+                        $($variant => $crate::solution!(@variant_fn $variant $(=> $func)?)),+
+                    ];
+                    std::hint::black_box((select_variant(&variants).run)(input));
+                }),
+            )+],
+            run: |input| {$(
+                let variants = $crate::variants![
+                    $($variant => $crate::solution!(@variant_fn $variant $(=> $func)?)),+
+                ];
+                run_variants(&variants, &input, DAY, $crate::solution!(@part_number $part));
+            )+}
+        );
+    };
+
+    (@variant_fn $variant:ident) => { $variant };
+    (@variant_fn $variant:ident => $func:path) => { $func };
+    (@part_number part_one) => { 1 };
+    (@part_number part_two) => { 2 };
+
+    (@impl_parsed $day:expr, $solution:ident, $( [$func:ident, $part:expr] )*) => {
+        $crate::solution!(@main $day,
+            fuzz: |input| { std::hint::black_box($solution::parse(input)); },
+            stress: &[$(
+                (part_name($part), &|input| {
+                    let parsed = $solution::parse(input);
+                    std::hint::black_box($solution::$func(&parsed));
+                }),
+            )*],
+            run: |input| {
+                let parsed = run_parse($solution::parse, &input);
+                $( run_part($solution::$func, &parsed, DAY, $part); )*
+            }
+        );
+    };
+
+    (@impl_parsed_solve $day:expr, $solution:ident) => {
+        $crate::solution!(@main $day,
+            fuzz: |input| { std::hint::black_box($solution::parse(input)); },
+            stress: &[
+                ("Solve", &|input| {
+                    let parsed = $solution::parse(input);
+                    std::hint::black_box($solution::solve(&parsed));
+                }),
+            ],
+            run: |input| {
+                let parsed = run_parse($solution::parse, &input);
+                run_solve($solution::solve, &parsed, DAY);
+            }
+        );
+    };
+
+    // NOTE: the arms above only differ in what they fuzz, stress and run, the shared `main` lives here.
+    (@main $day:expr, $(check: $check:expr,)? fuzz: $fuzz:expr, stress: $stress:expr, run: |$input:ident| $run:block) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +196,132 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[allow(unused_imports)]
+        use $crate::template::fuzz::fallback::*;
+        #[allow(unused_imports)]
+        use $crate::template::params::fallback::*;
+        #[allow(unused_imports)]
+        use $crate::template::stress::fallback::*;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[allow(unused_imports)]
+            use $crate::template::Solution;
+            $crate::template::log::init(DAY);
+            $crate::template::params::check(PARAMS);
+            if let Some(settings) = $crate::template::fuzz::requested() {
+                let target = FUZZ_TARGET.unwrap_or($fuzz);
+                return $crate::template::fuzz::run(DAY, target, &settings);
+            }
+            start_timeout(DAY);
+            $( $check; )?
+            if let Some(settings) = $crate::template::stress::requested() {
+                let steps: &[$crate::template::stress::Step] = $stress;
+                return $crate::template::stress::run(DAY, GENERATOR, &settings, steps);
+            }
+            let $input = $crate::template::read_file("inputs", DAY);
+            $run
+        }
+    };
+}
+
+/// Registers a generator for `cargo stress`, a function `generate(seed: u64, size: usize) -> String`
+/// that returns a puzzle input of the given size, e.g. `generator!(generate)`.
+///
+/// The same seed and size must always produce the same input, e.g. by drawing all random numbers
+/// from [`Rng::new(seed)`](crate::differential::Rng).
+#[macro_export]
+macro_rules! generator {
+    ($generate:path) => {
+        const GENERATOR: Option<$crate::template::stress::Generator> = Some($generate);
+    };
+}
+
+/// Declares puzzle parameters that differ between examples and real inputs, with the defaults for
+/// real inputs, e.g. `params!(width: usize = 101, height: usize = 103)`. Parts read them with `params()`.
+///
+/// Examples override them in the manifest, e.g. `params.width = 11`, and `cargo solve` with
+/// `--param width=11`. See [`crate::template::params`].
+#[macro_export]
+macro_rules! params {
+    ($($name:ident: $type:ty = $default:expr),+ $(,)?) => {
+        /// Parameters of the puzzle, see [`params`].
+        #[derive(Clone, Debug)]
+        struct Params {
+            $($name: $type,)+
+        }
+
+        /// Names of the parameters, to reject unknown overrides.
+        const PARAMS: &[&str] = &[$(stringify!($name)),+];
+
+        /// The parameters of the current input: the defaults, unless overridden by the example or `--param`.
+        fn params() -> Params {
+            Params {
+                $($name: $crate::template::params::get(stringify!($name), $default),)+
+            }
+        }
+    };
+}
+
+/// Registers the parse path that `cargo fuzz` feeds mutated examples into, a function or closure taking
+/// the input, e.g. `fuzz_target!(parse_ordering_rules)`. Days without a fuzz target fuzz both parts.
+///
+/// The result of the target is discarded, so it may return anything, e.g. `Option` to skip inputs
+/// that a day rejects on purpose.
+#[macro_export]
+macro_rules! fuzz_target {
+    ($target:expr) => {
+        const FUZZ_TARGET: Option<$crate::template::fuzz::Target> = Some(|input: &str| {
+            std::hint::black_box(($target)(input));
+        });
+    };
+}
+
+/// Generates a test for every example listed in the manifest `data/examples/NN.toml` of a day.
+///
+/// Pass the same solution as to [`solution!`](crate::solution), e.g. `example_tests!(6, Day06)` for a type
+/// implementing [`Solution`](crate::template::Solution). Days that solve both parts with a free function
+/// `solve` use `example_tests!(6, solve)`.
+#[macro_export]
+macro_rules! example_tests {
+    ($day:literal) => {
+        $crate::example_tests!(@impl $day, |input: &str, part: u8| match part {
+            1 => part_one(input).map(|result| result.to_string()),
+            _ => part_two(input).map(|result| result.to_string()),
+        });
+    };
+    ($day:literal, solve) => {
+        $crate::example_tests!(@impl $day, |input: &str, part: u8| {
+            let (part_1, part_2) = solve(input);
+            match part {
+                1 => part_1.map(|result| result.to_string()),
+                _ => part_2.map(|result| result.to_string()),
+            }
+        });
+    };
+    ($day:literal, $solution:ident) => {
+        $crate::example_tests!(@impl $day, |input: &str, part: u8| {
+            use $crate::template::Solution;
+            let parsed = $solution::parse(input);
+            match part {
+                1 => $solution::part_one(&parsed).map(|result| result.to_string()),
+                _ => $solution::part_two(&parsed).map(|result| result.to_string()),
+            }
+        });
+    };
+
+    (@impl $day:literal, $solve_part:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn run_example(index: usize, part: Option<u8>) {
+                $crate::template::examples::check($crate::day!($day), index, part, PARAMS, $solve_part);
+            }
+
+            // NOTE: one `#[test]` per part of every example, generated by `build.rs` from the manifest.
+            include!(concat!(env!("OUT_DIR"), "/example_tests/", $day, ".rs"));
         }
     };
 }
@@ -0,0 +1,176 @@
+/// Puzzle parameters that differ between examples and real inputs, e.g. the size of a grid.
+///
+/// Declare them next to the solution with [`params!`](crate::params), which generates a `Params` struct
+/// with the defaults for real inputs and a function `params()` that parts call to read them. Override
+/// them with `cargo solve <day> --param width=11`, or per example with `params.width = 11` in the
+/// manifest `data/examples/NN.toml`.
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::{env, process};
+
+/// Fallback for days without [`params!`](crate::params). `solution!` glob-imports this module,
+/// so the `PARAMS` defined by `params!` takes precedence.
+pub mod fallback {
+    pub const PARAMS: &[&str] = &[];
+}
+
+/// A parameter name with the value it is set to, e.g. `("width", "11")`.
+pub type Override = (String, String);
+
+thread_local! {
+    /// Overrides of the example that runs on this thread, see [`with_overrides`].
+    static EXAMPLE_OVERRIDES: RefCell<Option<Vec<Override>>> = const { RefCell::new(None) };
+}
+
+/// Parses an override like `width=11`.
+pub fn parse_override(s: &str) -> Result<Override, String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("`{s}` is not a parameter like `width=11`")),
+    }
+}
+
+/// The arguments that pass overrides to a solution.
+#[must_use]
+pub fn to_args(overrides: &[Override]) -> Vec<String> {
+    overrides
+        .iter()
+        .flat_map(|(name, value)| ["--param".to_string(), format!("{name}={value}")])
+        .collect()
+}
+
+fn from_args(args: &[String]) -> Vec<Override> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--param")
+        .filter_map(|pair| parse_override(&pair[1]).ok())
+        .collect()
+}
+
+/// Overrides passed with `--param`, read once since parts look them up on every run.
+fn command_line() -> &'static [Override] {
+    static OVERRIDES: OnceLock<Vec<Override>> = OnceLock::new();
+    OVERRIDES.get_or_init(|| from_args(&env::args().collect::<Vec<_>>()))
+}
+
+/// Runs `f` with the overrides of an example instead of those passed with `--param`.
+pub fn with_overrides<T>(overrides: &[Override], f: impl FnOnce() -> T) -> T {
+    let previous = EXAMPLE_OVERRIDES.with(|cell| cell.replace(Some(overrides.to_vec())));
+    let result = f();
+    EXAMPLE_OVERRIDES.with(|cell| cell.replace(previous));
+    result
+}
+
+/// Reads a parameter, returning `default` if it is not overridden. Used by [`params!`](crate::params).
+///
+/// # Panics
+///
+/// If the override can not be parsed as `T`.
+#[must_use]
+pub fn get<T: FromStr>(name: &str, default: T) -> T
+where
+    T::Err: Display,
+{
+    let value = EXAMPLE_OVERRIDES.with(|cell| match cell.borrow().as_ref() {
+        Some(overrides) => find(overrides, name),
+        None => find(command_line(), name),
+    });
+
+    match value {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|e| panic!("invalid value `{value}` for parameter `{name}`: {e}")),
+        None => default,
+    }
+}
+
+fn find(overrides: &[Override], name: &str) -> Option<String> {
+    overrides
+        .iter()
+        .rev()
+        .find(|(overridden, _)| overridden == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// The overrides whose name is not one of `declared`.
+#[must_use]
+pub fn unknown<'a>(overrides: &'a [Override], declared: &[&str]) -> Vec<&'a str> {
+    overrides
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| !declared.contains(name))
+        .collect()
+}
+
+/// Exits if a parameter passed with `--param <name>=<value>` is not one of `declared`.
+pub fn check(declared: &[&str]) {
+    let unknown = unknown(command_line(), declared);
+    let Some(name) = unknown.first() else {
+        return;
+    };
+
+    if declared.is_empty() {
+        eprintln!("Unknown parameter `{name}`, this day declares no parameters.");
+    } else {
+        eprintln!(
+            "Unknown parameter `{name}`, expected one of: {}.",
+            declared.join(", ")
+        );
+    }
+    process::exit(1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_args, get, parse_override, to_args, unknown, with_overrides};
+
+    fn overrides(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_override("width = 11"),
+            Ok(("width".into(), "11".into()))
+        );
+        assert_eq!(parse_override("width").is_err(), true);
+        assert_eq!(parse_override("=11").is_err(), true);
+    }
+
+    #[test]
+    fn passes_overrides_as_arguments() {
+        let overrides = overrides(&[("width", "11"), ("height", "7")]);
+        let mut args = vec!["target/release/14".to_string(), "--submit".to_string()];
+        args.extend(to_args(&overrides));
+        assert_eq!(from_args(&args), overrides);
+    }
+
+    #[test]
+    fn reads_overrides_of_examples() {
+        assert_eq!(get("width", 101), 101);
+        let width = with_overrides(&overrides(&[("width", "11")]), || get("width", 101));
+        assert_eq!(width, 11);
+        assert_eq!(get("width", 101), 101);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_values() {
+        with_overrides(&overrides(&[("width", "wide")]), || get("width", 101));
+    }
+
+    #[test]
+    fn finds_unknown_parameters() {
+        let overrides = overrides(&[("width", "11"), ("depth", "3")]);
+        assert_eq!(unknown(&overrides, &["width", "height"]), vec!["depth"]);
+    }
+}
@@ -0,0 +1,104 @@
+/// Timing of phases inside a solution, e.g. parsing vs. solving.
+///
+/// Phases are only measured while the runner collects them, calling [`span`] or [`phase`] from tests
+/// or other code is a no-op apart from the call itself.
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static PHASES: RefCell<Option<Vec<(&'static str, Duration)>>> = const { RefCell::new(None) };
+}
+
+/// Measures the phase `name` until the returned guard is dropped.
+/// Phases with the same name are added up.
+///
+/// ```
+/// use advent_of_code::template::phase::span;
+///
+/// let _parse = span("parse");
+/// let numbers: Vec<u32> = "1 2 3".split(' ').map(|n| n.parse().unwrap()).collect();
+/// drop(_parse);
+/// ```
+#[must_use]
+pub fn span(name: &'static str) -> Span {
+    let is_recording = PHASES.with_borrow(Option::is_some);
+    Span {
+        name,
+        start: is_recording.then(Instant::now),
+    }
+}
+
+/// Measures the phase `name` while running `f`.
+///
+/// ```
+/// use advent_of_code::template::phase::phase;
+///
+/// let numbers: Vec<u32> = phase("parse", || "1 2 3".split(' ').map(|n| n.parse().unwrap()).collect());
+/// ```
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let _span = span(name);
+    f()
+}
+
+/// Guard returned by [`span`].
+pub struct Span {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            add(self.name, start.elapsed());
+        }
+    }
+}
+
+fn add(name: &'static str, duration: Duration) {
+    PHASES.with_borrow_mut(|phases| {
+        let Some(phases) = phases else {
+            return;
+        };
+        match phases.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, total)) => *total += duration,
+            None => phases.push((name, duration)),
+        }
+    });
+}
+
+/// Runs `f` and returns the total duration of each phase it measured, in order of first occurrence.
+pub(crate) fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<(&'static str, Duration)>) {
+    let previous = PHASES.replace(Some(vec![]));
+    let result = f();
+    let phases = PHASES.replace(previous).unwrap_or_default();
+    (result, phases)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, phase, span};
+
+    #[test]
+    fn collects_phases_in_order() {
+        let (result, phases) = collect(|| {
+            let parsed = phase("parse", || 21);
+            for _ in 0..3 {
+                let _span = span("solve");
+            }
+            let _span = span("parse");
+            parsed * 2
+        });
+        assert_eq!(result, 42);
+        let names: Vec<_> = phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["parse", "solve"]);
+    }
+
+    #[test]
+    fn ignores_spans_outside_of_collect() {
+        let _span = span("parse");
+        assert!(span("solve").start.is_none());
+        assert!(collect(|| ()).1.is_empty());
+    }
+}
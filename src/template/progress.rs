@@ -0,0 +1,238 @@
+/// Module that tracks stars from local data and updates the readme with them.
+/// The table has the same format as the one of the `advent-readme-stars` action.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Stars earned per day, determined from verified answers and correct submissions.
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    /// Whether part 1 and part 2 are solved, indexed by day.
+    solved: Vec<(Day, [bool; 2])>,
+}
+
+impl Progress {
+    /// Reads `data/answers.json` and `data/submissions.json`, no network access is needed.
+    #[must_use]
+    pub fn from_local_data() -> Self {
+        Progress::new(&Answers::read_from_file(), &Submissions::read_from_file())
+    }
+
+    #[must_use]
+    pub fn new(answers: &Answers, submissions: &Submissions) -> Self {
+        let is_solved = |day: Day, part: u8| {
+            answers.get(day, part).is_some() || submissions.is_solved(day, part)
+        };
+
+        Progress {
+            solved: all_days()
+                .map(|day| (day, [is_solved(day, 1), is_solved(day, 2)]))
+                .collect(),
+        }
+    }
+
+    /// Number of stars earned on a day.
+    #[must_use]
+    pub fn stars(&self, day: Day) -> usize {
+        self.solved
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map_or(0, |(_, parts)| parts.iter().filter(|p| **p).count())
+    }
+
+    #[must_use]
+    pub fn total_stars(&self) -> usize {
+        all_days().map(|day| self.stars(day)).sum()
+    }
+
+    #[must_use]
+    pub fn complete_days(&self) -> usize {
+        all_days().filter(|day| self.stars(*day) == 2).count()
+    }
+
+    /// One line summarizing the progress, e.g. `⭐ 28 / 50 · 14 of 25 days complete`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        format!(
+            "⭐ {} / 50 · {} of 25 days complete",
+            self.total_stars(),
+            self.complete_days()
+        )
+    }
+
+    /// A calendar of all days with `★` for earned and `☆` for missing stars, five days per line.
+    #[must_use]
+    pub fn calendar(&self) -> Vec<String> {
+        let days: Vec<Day> = all_days().collect();
+        days.chunks(5)
+            .map(|week| {
+                week.iter()
+                    .map(|day| {
+                        let stars = self.stars(*day);
+                        format!(
+                            "{:>2} {}{}",
+                            day.into_inner(),
+                            "★".repeat(stars),
+                            "☆".repeat(2 - stars)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("   ")
+            })
+            .collect()
+    }
+}
+
+fn construct_table(progress: &Progress, year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("## {year} Results"),
+        None => "## Results".into(),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, parts) in &progress.solved {
+        if !parts.iter().any(|p| *p) {
+            continue;
+        }
+
+        let day_cell = match year {
+            Some(year) => format!(
+                "[Day {}](https://adventofcode.com/{year}/day/{})",
+                day.into_inner(),
+                day.into_inner()
+            ),
+            None => format!("Day {}", day.into_inner()),
+        };
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+
+        lines.push(format!(
+            "| {day_cell} | {} | {} |",
+            star(parts[0]),
+            star(parts[1])
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**{}**", progress.summary()));
+    lines.push(String::new());
+    lines.push("```".into());
+    lines.extend(progress.calendar());
+    lines.push("```".into());
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &Progress, year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(progress, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(progress: &Progress, year: Option<u16>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress, year)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Progress, MARKER};
+    use crate::{
+        day,
+        template::answers::Answers,
+        template::submissions::{Submission, Submissions, Verdict},
+    };
+
+    fn get_mock_progress() -> Progress {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+        answers.set(day!(1), 2, "31");
+        answers.set(day!(2), 1, "2");
+
+        let submissions = Submissions {
+            data: vec![
+                Submission {
+                    day: day!(2),
+                    part: 2,
+                    answer: "3".into(),
+                    verdict: Verdict::Incorrect,
+                    timestamp: 0,
+                },
+                Submission {
+                    day: day!(6),
+                    part: 1,
+                    answer: "41".into(),
+                    verdict: Verdict::Correct,
+                    timestamp: 0,
+                },
+            ],
+        };
+
+        Progress::new(&answers, &submissions)
+    }
+
+    #[test]
+    fn counts_stars() {
+        let progress = get_mock_progress();
+        assert_eq!(progress.stars(day!(1)), 2);
+        assert_eq!(progress.stars(day!(2)), 1);
+        assert_eq!(progress.stars(day!(6)), 1);
+        assert_eq!(progress.total_stars(), 4);
+        assert_eq!(progress.complete_days(), 1);
+        assert_eq!(progress.summary(), "⭐ 4 / 50 · 1 of 25 days complete");
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let calendar = get_mock_progress().calendar();
+        assert_eq!(calendar.len(), 5);
+        assert_eq!(calendar[0], " 1 ★★    2 ★☆    3 ☆☆    4 ☆☆    5 ☆☆");
+        assert_eq!(calendar[1].starts_with(" 6 ★☆"), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+    }
+
+    #[test]
+    fn updates_progress() {
+        let mut s = format!("foo\n{MARKER}\n\nbar");
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2024 Results").count(), 1);
+        assert_eq!(
+            s.contains("| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |"),
+            true
+        );
+        assert_eq!(s.contains("Day 3"), false);
+        assert_eq!(s.contains("**⭐ 4 / 50 · 1 of 25 days complete**"), true);
+        assert_eq!(s.ends_with(&format!("{MARKER}\n\nbar")), true);
+    }
+}
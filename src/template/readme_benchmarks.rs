@@ -1,8 +1,11 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io};
 
-use crate::template::timings::Timings;
+use crate::template::answers::Answers;
+use crate::template::aoc_cli;
+use crate::template::config::{self, Column, ReadmeConfig, Sort};
+use crate::template::timings::{Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,22 +24,18 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the block between two `marker`s, or the marker itself if it's only present once.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "Too many occurrences of marker `{marker}` in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,38 +51,212 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    total_millis: f64,
+    context: &Context,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    lines.extend(markdown_table(timings, context));
+    lines.push(String::new());
+    lines.push(format!(
+        "**Total: {}**",
+        context.config.total_unit.format(total_millis)
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// Everything besides the timings that the table can show.
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    pub config: ReadmeConfig,
+    pub answers: Answers,
+    /// Puzzle titles, read from the downloaded puzzle descriptions.
+    pub titles: HashMap<Day, String>,
+    pub year: Option<u16>,
+}
+
+impl Context {
+    /// Loads the readme config, verified answers and puzzle titles of the benchmarked days.
+    pub fn load(timings: &Timings) -> Self {
+        Context {
+            config: config::get().readme.clone(),
+            answers: Answers::read_from_file(),
+            titles: timings
+                .data
+                .iter()
+                .filter_map(|t| Some((t.day, read_puzzle_title(t.day)?)))
+                .collect(),
+            year: aoc_cli::get_year(),
+        }
+    }
+}
+
+/// Reads the title from a puzzle description downloaded by aoc-cli, e.g. `--- Day 1: Historian Hysteria ---`.
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()?;
+    puzzle.lines().find_map(|line| {
+        let title = line.trim_start_matches('#').trim();
+        let title = title.strip_prefix("--- ")?.strip_suffix(" ---")?;
+        Some(title.split_once(": ")?.1.to_string())
+    })
+}
+
+/// Renders timings as the lines of a markdown table.
+pub fn markdown_table(timings: &Timings, context: &Context) -> Vec<String> {
+    let config = &context.config;
+    let has_memory = timings.data.iter().any(|t| !t.memory.is_empty());
+
+    // the memory column is only shown once there is something to show.
+    let columns: Vec<Column> = config
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| *column != Column::Memory || has_memory)
+        .collect();
+
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+    if config.sort == Sort::Duration {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let mut by_duration = data.clone();
+    by_duration.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    let slowest: Vec<Day> = by_duration
+        .iter()
+        .take(config.highlight_slowest)
+        .map(|t| t.day)
+        .collect();
+
+    let mut header = vec!["Day"];
+    header.extend(columns.iter().flat_map(|column| column_headers(*column)));
+
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", header.join(" | ")),
+        format!("|{}", " :---: |".repeat(header.len())),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    for timing in data {
+        let link = config.day_link.replace("{day}", &timing.day.to_string());
+        let mut day = format!("[Day {}]({link})", timing.day.into_inner());
+        if slowest.contains(&timing.day) {
+            day = format!("**{day}**");
+        }
+
+        let mut cells = vec![day];
+        for column in &columns {
+            cells.extend(column_cells(*column, timing, context));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines
+}
 
-    lines.join("\n")
+fn column_headers(column: Column) -> Vec<&'static str> {
+    match column {
+        Column::Title => vec!["Puzzle"],
+        Column::Parse => vec!["Parse"],
+        Column::Part1 => vec!["Part 1"],
+        Column::Part2 => vec!["Part 2"],
+        Column::Memory => vec!["Memory 1", "Memory 2"],
+        Column::Samples => vec!["Samples"],
+        Column::Verified => vec!["Verified"],
+        Column::Stars => vec!["Stars"],
+    }
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn column_cells(column: Column, timing: &Timing, context: &Context) -> Vec<String> {
+    let time = |time: Option<&str>| format!("`{}`", time.unwrap_or("-"));
+
+    match column {
+        Column::Title => vec![match (context.titles.get(&timing.day), context.year) {
+            (Some(title), Some(year)) => format!(
+                "[{title}](https://adventofcode.com/{year}/day/{})",
+                timing.day.into_inner()
+            ),
+            (Some(title), None) => title.clone(),
+            (None, _) => "-".into(),
+        }],
+        Column::Parse => vec![time(timing.parse.as_deref())],
+        Column::Part1 => vec![match &timing.solve {
+            // both parts are timed as one unit, the time spans both columns.
+            Some(solve) => time(Some(solve)),
+            None => time(timing.part_1.as_deref()),
+        }],
+        Column::Part2 => vec![match &timing.solve {
+            Some(_) => "_(combined)_".into(),
+            None => time(timing.part_2.as_deref()),
+        }],
+        Column::Memory => {
+            let memory_of = |steps: &[&str]| {
+                timing
+                    .memory
+                    .iter()
+                    .find(|m| steps.contains(&m.step.as_str()))
+                    .map_or_else(
+                        || "-".into(),
+                        |m| format!("`{}` / {} allocs", m.peak, m.allocations),
+                    )
+            };
+            vec![memory_of(&["part_1", "solve"]), memory_of(&["part_2"])]
+        }
+        Column::Samples => {
+            let samples: Vec<String> = ["parse", "solve", "part_1", "part_2"]
+                .iter()
+                .filter_map(|step| timing.samples.get(*step))
+                .map(ToString::to_string)
+                .collect();
+            vec![if samples.is_empty() {
+                "-".into()
+            } else {
+                samples.join(" / ")
+            }]
+        }
+        Column::Verified => vec![verified_status(timing, &context.answers).into()],
+        Column::Stars => vec![match context.answers.stars(timing.day) {
+            0 => "-".into(),
+            stars => "⭐".repeat(stars),
+        }],
+    }
+}
+
+/// `✔` if all benchmarked answers match a verified answer, `✖` if any differs and `-` if none is known.
+fn verified_status(timing: &Timing, answers: &Answers) -> &'static str {
+    let mut checked = 0;
+    for (part, step) in [(1, "part_1"), (2, "part_2")] {
+        let (Some(expected), Some(actual)) =
+            (answers.get(timing.day, part), timing.answers.get(step))
+        else {
+            continue;
+        };
+        if expected != actual {
+            return "✖";
+        }
+        checked += 1;
+    }
+
+    if checked == 0 {
+        "-"
+    } else {
+        "✔"
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    context: &Context,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", &timings, total_millis, context);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,15 +265,23 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let context = Context::load(&timings);
+    update_content(&mut readme, timings, total_millis, &context)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::{update_content, Context, MARKER};
+    use crate::{
+        day,
+        template::answers::Answers,
+        template::config::{Column, ReadmeConfig, Sort, TotalUnit},
+        template::timings::{MemoryUsage, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,19 +290,40 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 3e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 7e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 9e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
             ],
         }
@@ -131,28 +333,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +362,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &Context::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,7 +370,7 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
+            "| :---: | :---: | :---: |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
@@ -180,4 +382,97 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_combined_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1 = None;
+        timings.data[1].part_2 = None;
+        timings.data[1].solve = Some("55ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &Context::default()).unwrap();
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `55ms` | _(combined)_ |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_memory_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory = vec![MemoryUsage {
+            step: "part_1".into(),
+            peak: "1.2 KiB".into(),
+            allocations: 42,
+            allocated: "5.0 KiB".into(),
+        }];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &Context::default()).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.2 KiB` / 42 allocs | - |"
+            ),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].samples = BTreeMap::from([("part_1".into(), 100), ("part_2".into(), 50)]);
+        timings.data[0].answers = BTreeMap::from([("part_1".into(), "42".into())]);
+        timings.data[1].answers = BTreeMap::from([("part_1".into(), "7".into())]);
+
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 2, "43");
+        answers.set(day!(2), 1, "8");
+
+        let context = Context {
+            config: ReadmeConfig {
+                columns: vec![
+                    Column::Title,
+                    Column::Part1,
+                    Column::Samples,
+                    Column::Verified,
+                    Column::Stars,
+                ],
+                sort: Sort::Duration,
+                highlight_slowest: 1,
+                day_link: "https://example.com/{day}".into(),
+                total_unit: TotalUnit::Seconds,
+            },
+            answers,
+            titles: HashMap::from([(day!(1), "Historian Hysteria".to_string())]),
+            year: Some(2024),
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &context).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Puzzle | Part 1 | Samples | Verified | Stars |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| **[Day 4](https://example.com/04)** | - | `40ms` | - | - | - |",
+            "| [Day 2](https://example.com/02) | - | `30ms` | - | ✖ | ⭐ |",
+            "| [Day 1](https://example.com/01) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | `10ms` | 100 / 50 | ✔ | ⭐⭐ |",
+            "",
+            "**Total: 0.19s**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
@@ -1,38 +1,79 @@
-use std::{collections::HashSet, io};
+use std::collections::HashSet;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Error, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the solutions of the given days. With `is_memory`, every solution is run a second time in a
+/// dhat build to record its heap usage, so that profiling does not distort the timings.
+///
+/// A day that fails does not stop the other days. Returns the timings of the days that ran, and an
+/// error listing the failed days if there were any.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_memory: bool,
+    extra_args: &[String],
+) -> (Option<Timings>, Result<(), Error>) {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed_days: Vec<Day> = vec![];
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = match child_commands::run_solution(day, is_timed, is_release, extra_args) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Error: {e}");
+                failed_days.push(day);
+                continue;
+            }
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output, day);
+
+            if is_memory {
+                println!("{ANSI_ITALIC}Memory (dhat){ANSI_RESET}");
+                match child_commands::run_memory_profile(day) {
+                    Ok(output) => val.memory = child_commands::parse_memory_usage(&output),
+                    Err(e) => {
+                        eprintln!("Error: {e}");
+                        failed_days.push(day);
+                    }
+                }
             }
-        });
+
+            timings.push(val);
+        }
+    }
+
+    let result = if failed_days.is_empty() {
+        Ok(())
+    } else {
+        let days: Vec<String> = failed_days.iter().map(Day::to_string).collect();
+        Err(Error::Other(format!(
+            "{} of {} days failed: {}.",
+            failed_days.len(),
+            days_to_run.len(),
+            days.join(", ")
+        )))
+    };
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -40,22 +81,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        (Some(timings), result)
     } else {
-        None
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+        (None, result)
     }
 }
 
@@ -67,9 +95,12 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::get_path_for_bin;
+    use crate::template::runner::{MEMORY_PREFIX, PHASE_PREFIX};
+    use crate::template::timings::{parse_duration_nanos, MemoryUsage, PhaseTiming};
+    use crate::template::{Day, Error};
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
@@ -77,7 +108,12 @@ pub mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        extra_args: &[String],
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -90,42 +126,89 @@ pub mod child_commands {
             args.push("--release");
         }
 
+        if is_timed || !extra_args.is_empty() {
+            args.push("--");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        args.extend(extra_args.iter().map(String::as_str));
+
+        run_cargo(&args, false)
+    }
+
+    /// Run the solution bin for a given day in a dhat build, which prints the heap usage of each part.
+    pub fn run_memory_profile(day: Day) -> Result<Vec<String>, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(vec![]);
+        }
+
+        let day_padded = day.to_string();
+        let args = vec![
+            "run",
+            "--quiet",
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--bin",
+            &day_padded,
+        ];
+
+        run_cargo(&args, true)
+    }
+
+    /// Spawns cargo with piped stdout/stderr.
+    /// Forwards output to stdout/stderr while grabbing stdout lines. With `is_memory_run`,
+    /// phases and the summary that dhat writes to stderr are not forwarded.
+    /// Fails if the solution exits with a non-zero status, e.g. because it panicked.
+    fn run_cargo(args: &[&str], is_memory_run: bool) -> Result<Vec<String>, Error> {
+        let command = format!("cargo {}", args.join(" "));
+        let broken_pipe = || Error::Other(format!("Failed to read the output of `{command}`."));
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|e| Error::io("Failed to run cargo", e))?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                if !(is_memory_run && line.starts_with("dhat: ")) {
+                    eprintln!("{line}");
+                }
             });
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
+            let line = line
+                .map_err(|e| Error::io(format!("Failed to read the output of `{command}`"), e))?;
+            if !(is_memory_run && line.starts_with(PHASE_PREFIX)) {
+                println!("{line}");
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        thread.join().map_err(|_| broken_pipe())?;
+        let status = cmd
+            .wait()
+            .map_err(|e| Error::io(format!("Failed to wait for `{command}`"), e))?;
 
-        Ok(output)
+        if status.success() {
+            Ok(output)
+        } else {
+            Err(Error::ChildFailed { command, status })
+        }
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -133,60 +216,206 @@ pub mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
+            solve: None,
             total_nanos: 0_f64,
+            phases: vec![],
+            memory: vec![],
+            samples: BTreeMap::new(),
+            spreads: BTreeMap::new(),
+            answers: BTreeMap::new(),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        // phase lines follow the line of the step they belong to.
+        let mut current_step = None;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        for l in output {
+            if let Some(phase) = l.strip_prefix(PHASE_PREFIX) {
+                let Some(step) = current_step else {
+                    continue;
                 };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                match parse_phase(phase, step) {
+                    Some(phase) => timings.phases.push(phase),
+                    None => eprintln!("Could not parse phase from line: {l}"),
                 }
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if let Some((step, answer)) = parse_answer(l) {
+                timings.answers.insert(step.into(), answer);
+            }
+
+            if !l.contains(" samples)") {
+                continue;
+            }
+
+            let Some((timing_str, nanos)) = parse_time(l) else {
+                eprintln!("Could not parse timings from line: {l}");
+                continue;
+            };
+
+            let Some(part) = l.split(':').next() else {
+                continue;
+            };
+
+            current_step = step_of(part);
+            match current_step {
+                Some("parse") => timings.parse = Some(timing_str.into()),
+                Some("solve") => timings.solve = Some(timing_str.into()),
+                Some("part_1") => timings.part_1 = Some(timing_str.into()),
+                Some("part_2") => timings.part_2 = Some(timing_str.into()),
+                _ => {}
+            }
+
+            if let (Some(step), Some(samples)) = (current_step, parse_samples(l)) {
+                timings.samples.insert(step.into(), samples);
+            }
+
+            if let (Some(step), Some(spread)) = (current_step, parse_spread(l)) {
+                timings.spreads.insert(step.into(), spread);
+            }
+
+            timings.total_nanos += nanos;
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parses the memory lines printed by a dhat build, see [`run_memory_profile`].
+    pub fn parse_memory_usage(output: &[String]) -> Vec<MemoryUsage> {
+        let mut memory = vec![];
+        let mut current_step = None;
+
+        for l in output {
+            if let Some(usage) = l.strip_prefix(MEMORY_PREFIX) {
+                let Some(step) = current_step else {
+                    continue;
+                };
+                match parse_memory(usage, step) {
+                    Some(usage) => memory.push(usage),
+                    None => eprintln!("Could not parse memory usage from line: {l}"),
+                }
+            } else if let Some((label, _)) = l.split_once(':') {
+                if let Some(step) = step_of(label) {
+                    current_step = Some(step);
+                }
+            }
+        }
+
+        memory
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
+    /// Maps the label of an output line, e.g. `Part 1`, to the step it reports.
+    fn step_of(label: &str) -> Option<&'static str> {
+        if label.contains("Parse") {
+            Some("parse")
+        } else if label.contains("Solve") {
+            Some("solve")
+        } else if label.contains("Part 1") {
+            Some("part_1")
+        } else if label.contains("Part 2") {
+            Some("part_2")
+        } else {
+            None
+        }
+    }
+
+    /// Parses the answer of a part from a line such as `Part 1: 42 (1.2ms @ 100 samples)`.
+    /// Missing and multi-line answers are skipped.
+    fn parse_answer(line: &str) -> Option<(&'static str, String)> {
+        // NOTE: the runner overwrites the intermediate result with `\r`, only the last segment is relevant.
+        let line = strip_ansi(line.rsplit('\r').next()?);
+        let (label, rest) = line.split_once(": ")?;
+        let step = step_of(label).filter(|step| step.starts_with("part_"))?;
+
+        let answer = match rest.trim_end().rsplit_once(" (") {
+            Some((answer, duration)) if duration.ends_with(')') => answer,
+            _ => rest,
+        }
+        .trim();
+
+        if answer.is_empty() || answer.starts_with('✖') || answer.starts_with('▼') {
+            None
+        } else {
+            Some((step, answer.into()))
+        }
+    }
+
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Removes the ANSI escape sequences the runner uses for formatting.
+    fn strip_ansi(line: &str) -> String {
+        let mut result = String::with_capacity(line.len());
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    /// Parses a memory line such as `1.2 KiB peak, 42 allocations, 5.0 KiB allocated`.
+    fn parse_memory(line: &str, step: &str) -> Option<MemoryUsage> {
+        let mut fields = line.split(", ");
+        let peak = fields.next()?.strip_suffix(" peak")?;
+        let allocations = fields.next()?.strip_suffix(" allocations")?.parse().ok()?;
+        let allocated = fields.next()?.strip_suffix(" allocated")?;
+        Some(MemoryUsage {
+            step: step.into(),
+            peak: peak.into(),
+            allocations,
+            allocated: allocated.into(),
+        })
+    }
+
+    /// Parses a phase line such as `parse_map: 1.2ms (40.0%)`.
+    fn parse_phase(line: &str, step: &str) -> Option<PhaseTiming> {
+        let (name, rest) = line.rsplit_once(": ")?;
+        let time = rest.split(" (").next()?.trim();
+        Some(PhaseTiming {
+            step: step.into(),
+            name: name.into(),
+            time: time.into(),
+        })
+    }
+
+    /// Splits the measurement of a line such as `Part 1: 42 (1.2ms ± 0.1ms @ 100 samples)` into the
+    /// time and its spread, if any.
+    fn parse_measurement(line: &str) -> Option<(&str, Option<&str>)> {
+        let measurement = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        Some(match measurement.split_once(" ± ") {
+            Some((time, spread)) => (time.trim(), Some(spread.trim())),
+            None => (measurement, None),
+        })
+    }
+
+    fn parse_time(line: &str) -> Option<(&str, f64)> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        let (str_timing, _) = parse_measurement(line)?;
+        Some((str_timing, parse_duration_nanos(str_timing)?))
+    }
 
-        Some((str_timing, parsed_timing))
+    fn parse_spread(line: &str) -> Option<f64> {
+        parse_duration_nanos(parse_measurement(line)?.1?)
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -205,7 +434,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_memory_usage};
 
         use crate::day;
 
@@ -214,7 +443,7 @@ pub mod child_commands {
             let res = parse_exec_time(
                 &[
                     "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 2: 10 (74.13ms ± 1.5ms @ 99999 samples)".into(),
                     "".into(),
                 ],
                 day!(1),
@@ -222,6 +451,10 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.samples["part_1"], 100000);
+            assert_eq!(res.samples["part_2"], 99999);
+            assert_eq!(res.spreads.contains_key("part_1"), false);
+            assert_approx_eq!(res.spreads["part_2"], 1_500_000_f64);
         }
 
         #[test]
@@ -237,6 +470,105 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
             assert_eq!(res.part_1.unwrap(), "2s");
             assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.answers["part_1"], "@ @ @ ( ) ms");
+            assert_eq!(res.answers["part_2"], "10s");
+        }
+
+        #[test]
+        fn parses_formatted_answers() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: \x1b[1m42\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.0ms @ 10 samples)".into(),
+                    "Part 2: ▼ (2.0ms @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.answers.get("part_1").map(String::as_str), Some("42"));
+            assert_eq!(res.answers.contains_key("part_2"), false);
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (1ms @ 100 samples)".into(),
+                    "  ↳ get_regions: 0.5ms (50.0%)".into(),
+                    "Part 1: 0 (10ms @ 100 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 11_000_000_f64);
+            assert_eq!(res.parse.unwrap(), "1ms");
+            assert_eq!(res.part_1.unwrap(), "10ms");
+            assert_eq!(res.phases[0].step, "parse");
+        }
+
+        #[test]
+        fn parses_combined_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 41".into(),
+                    "Part 2: 6".into(),
+                    "Solve: (4ms @ 100 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 4_000_000_f64);
+            assert_eq!(res.solve.unwrap(), "4ms");
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.answers["part_1"], "41");
+            assert_eq!(res.answers["part_2"], "6");
+            assert_eq!(res.samples["solve"], 100);
+        }
+
+        #[test]
+        fn parses_phases() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (10ms @ 100 samples)".into(),
+                    "  ↳ parse: 2.5ms (25.0%)".into(),
+                    "  ↳ solve: step 1: 7.5ms (75.0%)".into(),
+                    "Part 2: 10 (20ms @ 50 samples)".into(),
+                    "  ↳ parse: 2.5ms (12.5%)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 30_000_000_f64);
+            let phases: Vec<_> = res
+                .phases
+                .iter()
+                .map(|p| (p.step.as_str(), p.name.as_str(), p.time.as_str()))
+                .collect();
+            assert_eq!(
+                phases,
+                [
+                    ("part_1", "parse", "2.5ms"),
+                    ("part_1", "solve: step 1", "7.5ms"),
+                    ("part_2", "parse", "2.5ms")
+                ]
+            );
+        }
+
+        #[test]
+        fn parses_memory_usage() {
+            let res = parse_memory_usage(&[
+                "Parse: (1.0ms)".into(),
+                "  memory: 512 B peak, 3 allocations, 1.5 KiB allocated".into(),
+                "Part 1: 41 (2.0ms)".into(),
+                "  ↳ walk: 1.0ms (50.0%)".into(),
+                "  memory: 1.2 MiB peak, 42 allocations, 5.0 MiB allocated".into(),
+                "".into(),
+            ]);
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].step, "parse");
+            assert_eq!(res[0].peak, "512 B");
+            assert_eq!(res[1].step, "part_1");
+            assert_eq!(res[1].allocations, 42);
+            assert_eq!(res[1].allocated, "5.0 MiB");
         }
 
         #[test]
@@ -252,6 +584,7 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.answers.is_empty(), true);
         }
     }
 }
@@ -4,73 +4,368 @@ use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
+use crate::differential::Variant;
+use crate::template::submissions::{self, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, config, log, phase, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    run_labeled_part(func, input, day, part, &format!("Part {part}"));
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+fn run_labeled_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    part_str: &str,
+) {
+    let (result, measurement) = run_timed(func, input, |result| print_result(result, part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, part_str, &measurement.format_duration());
+    measurement.print_breakdown();
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run both parts at once with a function returning both answers, timed as one unit.
+pub fn run_solve<I: Copy, T1: Display, T2: Display>(
+    func: impl Fn(I) -> (Option<T1>, Option<T2>),
+    input: I,
+    day: Day,
+) {
+    let ((part_1, part_2), measurement) = run_timed(func, input, |(part_1, part_2)| {
+        print_answer(part_1, "Part 1");
+        print_answer(part_2, "Part 2");
+        print!("Solve:");
+        let _ = stdout().flush();
+    });
+
+    println!("\rSolve:{}", measurement.format_duration());
+    measurement.print_breakdown();
+
+    if let Some(result) = part_1 {
+        submit_result(result, day, 1);
+    }
+    if let Some(result) = part_2 {
+        submit_result(result, day, 2);
+    }
+}
+
+/// Run the variant of a part selected with `--variant <name>`, or its first variant.
+/// With `--variants`, every variant is run and compared to the first one.
+pub fn run_variants<T: Display>(
+    variants: &[Variant<str, Option<T>>],
+    input: &str,
+    day: Day,
+    part: u8,
+) {
+    if env::args().any(|x| x == "--variants") {
+        compare_variants(variants, input, part);
+        return;
+    }
+
+    let variant = select_variant(variants);
+    if selected_variant().as_deref() == Some(variant.name) {
+        let part_str = format!("Part {part} [{}]", variant.name);
+        run_labeled_part(variant.run, input, day, part, &part_str);
+    } else {
+        run_part(variant.run, input, day, part);
+    }
+}
+
+/// The variant selected with `--variant <name>`, or the first one if the part does not declare it.
+pub fn select_variant<I: ?Sized, O>(variants: &[Variant<I, O>]) -> &Variant<I, O> {
+    let selected = selected_variant();
+    variants
+        .iter()
+        .find(|variant| Some(variant.name) == selected.as_deref())
+        .unwrap_or(&variants[0])
+}
+
+/// The label of a part in the output, e.g. `Part 1`.
+#[must_use]
+pub fn part_name(part: u8) -> &'static str {
+    if part == 1 {
+        "Part 1"
+    } else {
+        "Part 2"
+    }
+}
+
+/// Exits if the variant passed with `--variant <name>` is not one of `names`.
+pub fn check_variant(names: &[&str]) {
+    let Some(selected) = selected_variant() else {
+        return;
+    };
+
+    if !names.contains(&selected.as_str()) {
+        let mut unique: Vec<&str> = vec![];
+        for name in names {
+            if !unique.contains(name) {
+                unique.push(name);
+            }
+        }
+        eprintln!(
+            "Unknown variant `{selected}`, expected one of: {}.",
+            unique.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+fn selected_variant() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--variant")?;
+    args.get(index + 1).cloned()
+}
+
+/// Runs every variant of a part, then prints how their durations and answers compare to the first one.
+fn compare_variants<T: Display>(variants: &[Variant<str, Option<T>>], input: &str, part: u8) {
+    let results: Vec<(&str, Option<String>, Duration)> = variants
+        .iter()
+        .map(|variant| {
+            let part_str = format!("Part {part} [{}]", variant.name);
+            let (result, measurement) = run_timed(variant.run, input, |result| {
+                print_result(result, &part_str, "");
+            });
+            print_result(&result, &part_str, &measurement.format_duration());
+            measurement.print_breakdown();
+            (
+                variant.name,
+                result.map(|result| result.to_string()),
+                measurement.duration,
+            )
+        })
+        .collect();
+
+    if results.len() < 2 {
+        return;
+    }
+    let (_, reference_result, reference_duration) = &results[0];
+
+    let width = results
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    println!("Comparison of part {part}:");
+    for (index, (name, result, duration)) in results.iter().enumerate() {
+        let speed = if index == 0 {
+            "reference".to_string()
+        } else {
+            format_speedup(*reference_duration, *duration)
+        };
+        let answer = if result == reference_result {
+            String::new()
+        } else {
+            format!(
+                ", {ANSI_BOLD}answer differs{ANSI_RESET}: {}",
+                result.as_deref().unwrap_or("✖")
+            )
+        };
+        println!(
+            "  {name:<width$}  {:>10}  {speed}{answer}",
+            format!("{duration:.1?}")
+        );
+    }
+}
+
+/// Describes how much faster or slower `duration` is than `reference`, e.g. `12.30x faster`.
+fn format_speedup(reference: Duration, duration: Duration) -> String {
+    let (reference, duration) = (reference.as_secs_f64(), duration.as_secs_f64());
+    if duration == 0.0 || reference == 0.0 {
+        "n/a".into()
+    } else if duration <= reference {
+        format!("{:.2}x faster", reference / duration)
+    } else {
+        format!("{:.2}x slower", duration / reference)
+    }
+}
+
+/// Aborts the solution once the timeout configured with `run.timeout_secs` in `aoc.toml` elapsed.
+pub fn start_timeout(day: Day) {
+    let Some(timeout) = config::get().run.timeout else {
+        return;
+    };
+
+    thread::spawn(move || {
+        thread::sleep(timeout);
+        eprintln!("Day {day} timed out after {timeout:?}.");
+        process::exit(1);
+    });
+}
+
+/// Run the parse step of a [`Solution`](crate::template::Solution), timed like a part.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) -> T {
+    let (parsed, measurement) = run_timed(func, input, |_| {
+        print!("Parse:");
+        let _ = stdout().flush();
+    });
+
+    println!("\rParse:{}", measurement.format_duration());
+    measurement.print_breakdown();
+
+    parsed
+}
+
+/// Heap usage of a single run, measured with dhat when the `dhat-heap` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub peak_bytes: usize,
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} allocations, {} allocated",
+            format_bytes(self.peak_bytes as u64),
+            self.allocations,
+            format_bytes(self.allocated_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with binary units, e.g. `12.3 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    // NOTE: precision loss is irrelevant for display.
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Result of timing a solution function.
+struct Measurement {
+    duration: Duration,
+    samples: u128,
+    /// Standard deviation of the benchmark samples.
+    spread: Duration,
+    /// Average duration of each phase marked with [`phase::span`].
+    phases: Vec<(&'static str, Duration)>,
+    memory: Option<MemoryStats>,
+}
+
+impl Measurement {
+    fn format_duration(&self) -> String {
+        format_duration(&self.duration, self.samples, &self.spread)
+    }
+
+    /// Prints the phases and memory usage below the result.
+    fn print_breakdown(&self) {
+        print_phases(&self.phases, &self.duration);
+        if let Some(memory) = self.memory {
+            println!("{MEMORY_PREFIX}{memory}");
+        }
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// With the `dhat-heap` feature, the heap usage of the first run is measured as well.
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let ((result, memory), phases) = phase::collect(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
-    };
+        let result = func(input);
+
+        #[cfg(feature = "dhat-heap")]
+        let memory = {
+            let stats = dhat::HeapStats::get();
+            Some(MemoryStats {
+                peak_bytes: stats.max_bytes,
+                allocations: stats.total_blocks,
+                allocated_bytes: stats.total_bytes,
+            })
+        };
+        #[cfg(not(feature = "dhat-heap"))]
+        let memory = None;
+
+        (result, memory)
+    });
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let (duration, samples, spread, phases) = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, 1, Duration::ZERO, phases)
     };
 
-    (result, run.0, run.1)
+    let measurement = Measurement {
+        duration,
+        samples,
+        spread,
+        phases,
+        memory,
+    };
+
+    (result, measurement)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Duration, Vec<(&'static str, Duration)>) {
     let mut stdout = stdout();
+    let _quiet = log::suppress();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let config = &config::get().benchmark;
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
-        let timer = Instant::now();
-        black_box(func(black_box(input)));
-        timers.push(timer.elapsed());
-    }
+    let ((), phases) = phase::collect(|| {
+        for _ in 0..bench_iterations {
+            let timer = Instant::now();
+            black_box(func(black_box(input)));
+            timers.push(timer.elapsed());
+        }
+    });
+
+    // NOTE: iterations are clamped to `benchmark.max_samples`, which is far below `u32::MAX` in practice.
+    #[allow(clippy::cast_possible_truncation)]
+    let phases = phases
+        .into_iter()
+        .map(|(name, total)| (name, total / bench_iterations as u32))
+        .collect();
+
+    let average = average_duration(&timers);
 
     (
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        Duration::from_nanos(average as u64),
         bench_iterations,
+        standard_deviation(&timers, average),
+        phases,
     )
 }
 
@@ -82,11 +377,47 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+// NOTE: precision loss is irrelevant for durations far below 2^52 nanoseconds.
+#[allow(clippy::cast_precision_loss)]
+fn standard_deviation(numbers: &[Duration], average: u128) -> Duration {
+    let variance = numbers
+        .iter()
+        .map(|duration| (duration.as_nanos() as f64 - average as f64).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    Duration::from_secs_f64(variance.sqrt() / 1e9)
+}
+
+fn format_duration(duration: &Duration, samples: u128, spread: &Duration) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({duration:.1?} ± {spread:.1?} @ {samples} samples)")
+    }
+}
+
+/// Prefix of the lines that break down the duration of a part into its phases.
+pub const PHASE_PREFIX: &str = "  ↳ ";
+
+/// Prefix of the line that shows the heap usage of a part.
+pub const MEMORY_PREFIX: &str = "  memory: ";
+
+fn print_phases(phases: &[(&str, Duration)], total: &Duration) {
+    for (name, duration) in phases {
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            duration.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        println!("{PHASE_PREFIX}{name}: {duration:.1?} ({share:.1}%)");
+    }
+}
+
+fn print_answer<T: Display>(result: &Option<T>, part: &str) {
+    match result {
+        Some(result) if result.to_string().contains('\n') => println!("{part}: ▼\n{result}"),
+        Some(result) => println!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => println!("{part}: ✖"),
     }
 }
 
@@ -161,5 +492,15 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let output = aoc_cli::submit(day, part, &answer);
+
+    if let Ok(output) = &output {
+        let verdict = Verdict::from_response(&String::from_utf8_lossy(&output.stdout));
+        if let Err(e) = submissions::record(day, part, &answer, verdict) {
+            eprintln!("Failed to record submission: {e}");
+        }
+    }
+
+    Some(output)
 }
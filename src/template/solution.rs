@@ -0,0 +1,50 @@
+/// Solutions that parse the input once and share it between both parts.
+use std::fmt::Display;
+
+/// A solution with a separate parse step, as an alternative to the free functions `part_one` and `part_two`.
+///
+/// Register it with `solution!(<day>, <type>)`. The runner times `parse` on its own and passes the
+/// parsed input to both parts.
+///
+/// ```
+/// use advent_of_code::template::Solution;
+///
+/// struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed = Vec<u32>;
+///     type Output1 = u32;
+///     type Output2 = u32;
+///
+///     fn parse(input: &str) -> Self::Parsed {
+///         input.lines().map(|line| line.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Option<u32> {
+///         numbers.iter().max().copied()
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Option<u32> {
+///         Some(numbers.iter().sum())
+///     }
+/// }
+///
+/// let numbers = Day01::parse("1\n2\n3");
+/// assert_eq!(Day01::part_one(&numbers), Some(3));
+/// ```
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Parsed;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Option<Self::Output1>;
+    fn part_two(parsed: &Self::Parsed) -> Option<Self::Output2>;
+
+    /// Solves both parts at once, used by `solution!(<day>, <type>, solve)`.
+    /// Override it if part two can reuse intermediate results of part one.
+    fn solve(parsed: &Self::Parsed) -> (Option<Self::Output1>, Option<Self::Output2>) {
+        (Self::part_one(parsed), Self::part_two(parsed))
+    }
+}
@@ -0,0 +1,237 @@
+/// Stress tests that run a solution on generated inputs of growing size to see how it scales.
+///
+/// Register a generator next to the solution with [`generator!`](crate::generator), then run
+/// `cargo stress <day> --sizes 10,100,1000`. The same seed and size always produce the same input.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use std::{env, process};
+
+use crate::template::runner::format_bytes;
+use crate::template::{log, Day};
+
+/// Generates a puzzle input from a seed and a size, e.g. the side length of a grid.
+pub type Generator = fn(u64, usize) -> String;
+
+/// A named step that is timed on every generated input, e.g. `Part 1`.
+pub type Step<'a> = (&'a str, &'a dyn Fn(&str));
+
+/// Fallback for days without [`generator!`](crate::generator). `solution!` glob-imports this module,
+/// so the `GENERATOR` defined by `generator!` takes precedence.
+pub mod fallback {
+    pub const GENERATOR: Option<super::Generator> = None;
+}
+
+/// Measurements of a step stop after this duration, or after [`MAX_SAMPLES`] runs.
+const MEASURE_BUDGET: Duration = Duration::from_millis(100);
+const MAX_SAMPLES: u32 = 100;
+
+/// Settings passed from `cargo stress` to a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub sizes: Vec<usize>,
+    pub seed: u64,
+}
+
+impl Settings {
+    /// The arguments that pass these settings to a solution.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let sizes: Vec<String> = self.sizes.iter().map(ToString::to_string).collect();
+        vec![
+            "--stress".into(),
+            "--sizes".into(),
+            sizes.join(","),
+            "--seed".into(),
+            self.seed.to_string(),
+        ]
+    }
+
+    fn from_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|arg| arg == "--stress") {
+            return None;
+        }
+
+        let value = |flag: &str| {
+            let index = args.iter().position(|arg| arg == flag)?;
+            args.get(index + 1)
+        };
+
+        Some(Settings {
+            sizes: value("--sizes")
+                .map(|sizes| parse_sizes(sizes).unwrap_or_default())
+                .unwrap_or_default(),
+            seed: value("--seed")
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Parses a comma separated list of sizes, e.g. `10,100,1000`.
+pub fn parse_sizes(s: &str) -> Result<Vec<usize>, String> {
+    s.split(',')
+        .map(|size| match size.trim().parse() {
+            Ok(size) if size > 0 => Ok(size),
+            _ => Err(format!("`{size}` is not a positive size")),
+        })
+        .collect()
+}
+
+/// Reads the settings of `cargo stress` from the command-line arguments of a solution.
+#[must_use]
+pub fn requested() -> Option<Settings> {
+    Settings::from_args(&env::args().collect::<Vec<_>>())
+}
+
+/// Times every step on the inputs generated for each size and prints a table with the
+/// estimated complexity exponent of each step.
+pub fn run(day: Day, generator: Option<Generator>, settings: &Settings, steps: &[Step]) {
+    let Some(generate) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Register one with `advent_of_code::generator!(generate);`."
+        );
+        process::exit(1);
+    };
+
+    let _quiet = log::suppress();
+
+    println!("Stress test of day {day} with seed {}", settings.seed);
+    let header: Vec<String> = steps.iter().map(|(name, _)| (*name).to_string()).collect();
+    print_row("size", "input", &header);
+
+    let mut durations: Vec<Vec<(usize, Duration)>> = vec![vec![]; steps.len()];
+
+    for &size in &settings.sizes {
+        let input = generate(settings.seed, size);
+        let row: Vec<String> = steps
+            .iter()
+            .zip(durations.iter_mut())
+            .map(|((_, step), durations)| {
+                let duration = measure(step, &input);
+                durations.push((size, duration));
+                format!("{duration:.1?}")
+            })
+            .collect();
+        print_row(&size.to_string(), &format_bytes(input.len() as u64), &row);
+    }
+
+    let exponents: Vec<String> = durations
+        .iter()
+        .map(|durations| match complexity_exponent(durations) {
+            Some(exponent) => format!("n^{exponent:.2}"),
+            None => "n/a".into(),
+        })
+        .collect();
+    print_row("exponent", "", &exponents);
+}
+
+fn print_row(size: &str, input: &str, columns: &[String]) {
+    let columns: String = columns
+        .iter()
+        .map(|column| format!("{column:>14}"))
+        .collect();
+    println!("{size:>10}{input:>12}{columns}");
+}
+
+/// Average duration of `step` on `input`, running it repeatedly within [`MEASURE_BUDGET`].
+fn measure(step: &dyn Fn(&str), input: &str) -> Duration {
+    let mut total = Duration::ZERO;
+    let mut samples = 0;
+
+    while samples < MAX_SAMPLES && (samples == 0 || total < MEASURE_BUDGET) {
+        let timer = Instant::now();
+        step(black_box(input));
+        total += timer.elapsed();
+        samples += 1;
+    }
+
+    total / samples
+}
+
+/// Fits `duration ≈ c * size^k` to the measurements and returns `k`, e.g. `1.0` for linear and `2.0`
+/// for quadratic steps. Requires at least two different sizes.
+#[must_use]
+pub fn complexity_exponent(measurements: &[(usize, Duration)]) -> Option<f64> {
+    // NOTE: precision loss is irrelevant for an estimate.
+    #[allow(clippy::cast_precision_loss)]
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .filter(|(size, duration)| *size > 0 && !duration.is_zero())
+        .map(|(size, duration)| ((*size as f64).ln(), duration.as_secs_f64().ln()))
+        .collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{complexity_exponent, parse_sizes, Settings};
+
+    #[test]
+    fn estimates_exponents() {
+        let linear = [
+            (10, Duration::from_micros(10)),
+            (100, Duration::from_micros(100)),
+            (1000, Duration::from_micros(1000)),
+        ];
+        let quadratic = [
+            (10, Duration::from_micros(1)),
+            (100, Duration::from_micros(100)),
+            (1000, Duration::from_micros(10000)),
+        ];
+        assert_eq!(
+            (complexity_exponent(&linear).unwrap() - 1.0).abs() < 1e-9,
+            true
+        );
+        assert_eq!(
+            (complexity_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9,
+            true
+        );
+    }
+
+    #[test]
+    fn needs_two_sizes_for_exponents() {
+        assert_eq!(complexity_exponent(&[(10, Duration::from_micros(5))]), None);
+        assert_eq!(
+            complexity_exponent(&[
+                (10, Duration::from_micros(5)),
+                (10, Duration::from_micros(6))
+            ]),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_sizes("10,100, 1000"), Ok(vec![10, 100, 1000]));
+        assert_eq!(parse_sizes("10,0").is_err(), true);
+        assert_eq!(parse_sizes("ten").is_err(), true);
+    }
+
+    #[test]
+    fn passes_settings_as_arguments() {
+        let settings = Settings {
+            sizes: vec![10, 100],
+            seed: 7,
+        };
+        let mut args = vec!["target/release/09".to_string()];
+        args.extend(settings.to_args());
+        assert_eq!(Settings::from_args(&args), Some(settings));
+        assert_eq!(Settings::from_args(&args[..1]), None);
+    }
+}
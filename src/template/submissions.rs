@@ -0,0 +1,293 @@
+/// Log of answers submitted via aoc-cli, stored in `data/submissions.json`.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::{config, Day};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// Response of the Advent of Code website to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// The answer was submitted too soon after a wrong one.
+    TooSoon,
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Determines the verdict from the response that aoc-cli prints.
+    #[must_use]
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::TooSoon
+        } else if response.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooSoon => "too_soon",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too_soon" => Ok(Verdict::TooSoon),
+            "wrong_level" => Ok(Verdict::WrongLevel),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// All submitted answers, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(config::get().paths.data_file(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(config::get().paths.data_file(SUBMISSIONS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Whether a part was ever submitted with a correct answer.
+    pub fn is_solved(&self, day: Day, part: u8) -> bool {
+        self.data
+            .iter()
+            .any(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+    }
+}
+
+/// Appends a submission to the log. A correct answer is also stored as verified answer.
+pub fn record(day: Day, part: u8, answer: &str, verdict: Verdict) -> Result<(), Error> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    let mut submissions = Submissions::read_from_file();
+    submissions.data.push(Submission {
+        day,
+        part,
+        answer: answer.into(),
+        verdict,
+        timestamp,
+    });
+    submissions.store_file()?;
+
+    if verdict == Verdict::Correct {
+        let mut answers = Answers::read_from_file();
+        answers.set(day, part, answer);
+        answers.store_file()?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        // NOTE: timestamps in seconds are well within the exact range of a float.
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        // NOTE: parts and timestamps are stored as whole numbers.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|n| *n as u64)
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let part = u8::try_from(number("part")?)
+            .ok()
+            .filter(|part| (1..=2).contains(part))
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|verdict| Verdict::from_str(verdict).ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp: number("timestamp")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Submissions, Verdict};
+    use crate::day;
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently."),
+            Verdict::TooSoon
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn handles_json_submissions() {
+        let json = r#"{ "data": [
+            { "day": "01", "part": 1, "answer": "10", "verdict": "incorrect", "timestamp": 1733000000 },
+            { "day": "01", "part": 1, "answer": "11", "verdict": "correct", "timestamp": 1733000100 }
+        ] }"#
+            .to_string();
+        let submissions = Submissions::try_from(json).unwrap();
+        assert_eq!(submissions.data.len(), 2);
+        assert_eq!(submissions.data[1].timestamp, 1_733_000_100);
+        assert_eq!(submissions.is_solved(day!(1), 1), true);
+        assert_eq!(submissions.is_solved(day!(1), 2), false);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_parts() {
+        let json = r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct", "timestamp": 0 }] }"#.to_string();
+        Submissions::try_from(json).unwrap();
+    }
+}
@@ -1,9 +1,18 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{config, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// The steps a [`Timing`] can contain, named like its keys in `timings.json`.
+pub const STEPS: [&str; 4] = ["parse", "part_1", "part_2", "solve"];
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,7 +20,52 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Time of the parse step, for days implementing [`crate::template::Solution`].
+    pub parse: Option<String>,
+    /// Time of both parts solved together, for days using `solution!(<day>, solve)`.
+    pub solve: Option<String>,
     pub total_nanos: f64,
+    pub phases: Vec<PhaseTiming>,
+    /// Heap usage per step, recorded by `cargo time --memory`.
+    pub memory: Vec<MemoryUsage>,
+    /// Number of benchmark samples per step.
+    pub samples: BTreeMap<String, u64>,
+    /// Standard deviation of the benchmark samples per step in nanoseconds, to tell changes from noise.
+    pub spreads: BTreeMap<String, f64>,
+    /// Answers printed per part while benchmarking, used to check them against verified answers.
+    pub answers: BTreeMap<String, String>,
+}
+
+impl Timing {
+    /// Time of one of the [`STEPS`], if it was measured.
+    pub fn step_time(&self, step: &str) -> Option<&str> {
+        match step {
+            "parse" => self.parse.as_deref(),
+            "part_1" => self.part_1.as_deref(),
+            "part_2" => self.part_2.as_deref(),
+            "solve" => self.solve.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Benchmark time of a phase inside a part, see [`crate::template::phase`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhaseTiming {
+    /// The step the phase belongs to: `part_1`, `part_2`, `parse` or `solve`, same as the keys of [`Timing`].
+    pub step: String,
+    pub name: String,
+    pub time: String,
+}
+
+/// Heap usage of a step, see [`crate::template::runner::MemoryStats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryUsage {
+    /// The step that was measured, same as [`PhaseTiming::step`].
+    pub step: String,
+    pub peak: String,
+    pub allocations: u64,
+    pub allocated: String,
 }
 
 /// Represents benchmark times for a set of days.
@@ -24,25 +78,50 @@ pub struct Timings {
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        self.store_to(&config::get().paths.data_file(TIMINGS_FILE_NAME))
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        Timings::read_from(&config::get().paths.data_file(TIMINGS_FILE_NAME)).unwrap_or_default()
+    }
+
+    /// Store timings as a named baseline next to `timings.json`, see [`baseline_path`].
+    pub fn store_baseline(&self, name: &str) -> Result<(), Error> {
+        self.store_to(&baseline_path(name))
+    }
+
+    /// Read a named baseline stored with [`Timings::store_baseline`].
+    pub fn read_baseline(name: &str) -> Result<Self, String> {
+        Timings::read_from(&baseline_path(name))
+            .map_err(|e| format!("could not read baseline `{name}`: {e}"))
+    }
+
+    fn store_to(&self, path: &Path) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    fn read_from(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
-            .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory usage of `self` is kept if `other` was measured without it.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.memory.is_empty() {
+                if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                    timing.memory.clone_from(&stored.memory);
+                }
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +130,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -61,9 +140,40 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+        self.data.iter().any(|t| {
+            t.day == day && (t.solve.is_some() || (t.part_1.is_some() && t.part_2.is_some()))
+        })
+    }
+}
+
+/// Path of a named baseline, e.g. `./data/timings.before-refactor.json`.
+#[must_use]
+pub fn baseline_path(name: &str) -> PathBuf {
+    config::get()
+        .paths
+        .data_file(&format!("timings.{name}.json"))
+}
+
+/// Baseline names end up in a file name, so they are restricted to letters, digits, `-` and `_`.
+#[must_use]
+pub fn is_valid_baseline_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parses a duration as printed by the runner, e.g. `74.13µs`, to nanoseconds.
+#[must_use]
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse_to_float = |postfix: &str| s.split(postfix).next()?.trim().parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse_to_float("ns"),
+        s if s.contains("µs") => parse_to_float("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float("ms").map(|x| x * 1_000_000_f64),
+        _ => parse_to_float("s").map(|x| x * 1_000_000_000_f64),
     }
 }
 
@@ -133,6 +243,67 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match value.parse.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "solve".into(),
+            match value.solve.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "phases".into(),
+            JsonValue::Array(value.phases.iter().map(JsonValue::from).collect()),
+        );
+
+        map.insert(
+            "memory".into(),
+            JsonValue::Array(value.memory.iter().map(JsonValue::from).collect()),
+        );
+
+        // NOTE: sample counts are small enough to be represented exactly.
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            JsonValue::Object(
+                value
+                    .samples
+                    .iter()
+                    .map(|(step, samples)| (step.clone(), JsonValue::Number(*samples as f64)))
+                    .collect(),
+            ),
+        );
+
+        map.insert(
+            "spreads".into(),
+            JsonValue::Object(
+                value
+                    .spreads
+                    .iter()
+                    .map(|(step, spread)| (step.clone(), JsonValue::Number(*spread)))
+                    .collect(),
+            ),
+        );
+
+        map.insert(
+            "answers".into(),
+            JsonValue::Object(
+                value
+                    .answers
+                    .iter()
+                    .map(|(step, answer)| (step.clone(), JsonValue::String(answer.clone())))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -161,16 +332,202 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: older timing files don't contain the parse and solve steps.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
+        let solve = json
+            .get("solve")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: phases were added later, older timing files don't contain them.
+        let phases = match json.get("phases") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.phases to be an array.")?
+                .iter()
+                .map(PhaseTiming::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
+        // NOTE: memory usage is only present for days timed with `--memory`.
+        let memory = match json.get("memory") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.memory to be an array.")?
+                .iter()
+                .map(MemoryUsage::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
+        // NOTE: sample counts and answers were added later, older timing files don't contain them.
+        let samples = match json.get("samples") {
+            None => BTreeMap::new(),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.samples to be an object.")?
+                .iter()
+                .map(|(step, samples)| {
+                    samples
+                        .get::<f64>()
+                        .map(|samples| (step.clone(), *samples as u64))
+                        .ok_or("Expected timing.samples to contain numbers.")
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        // NOTE: spreads were added later, older timing files don't contain them.
+        let spreads = match json.get("spreads") {
+            None => BTreeMap::new(),
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.spreads to be an object.")?
+                .iter()
+                .map(|(step, spread)| {
+                    spread
+                        .get::<f64>()
+                        .map(|spread| (step.clone(), *spread))
+                        .ok_or("Expected timing.spreads to contain numbers.")
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        let answers = match json.get("answers") {
+            None => BTreeMap::new(),
+            Some(v) => v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.answers to be an object.")?
+                .iter()
+                .map(|(step, answer)| {
+                    answer
+                        .get::<String>()
+                        .map(|answer| (step.clone(), answer.clone()))
+                        .ok_or("Expected timing.answers to contain strings.")
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse: parse.cloned(),
+            solve: solve.cloned(),
             total_nanos,
+            phases,
+            memory,
+            samples,
+            spreads,
+            answers,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PhaseTiming> for JsonValue {
+    fn from(value: &PhaseTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("step".into(), JsonValue::String(value.step.clone()));
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        map.insert("time".into(), JsonValue::String(value.time.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PhaseTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected phase to be a JSON object.")?;
+
+        let step = json
+            .get("step")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected phase.step to be a string.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected phase.name to be a string.")?;
+
+        let time = json
+            .get("time")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected phase.time to be a string.")?;
+
+        Ok(PhaseTiming {
+            step: step.clone(),
+            name: name.clone(),
+            time: time.clone(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryUsage> for JsonValue {
+    fn from(value: &MemoryUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("step".into(), JsonValue::String(value.step.clone()));
+        map.insert("peak".into(), JsonValue::String(value.peak.clone()));
+        // NOTE: JSON numbers are floats, precision loss only affects absurd allocation counts.
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "allocated".into(),
+            JsonValue::String(value.allocated.clone()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryUsage {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory usage to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected memory.{key} to be a string."))
+        };
+
+        // NOTE: allocation counts are stored as whole numbers.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let allocations = json
+            .get("allocations")
+            .and_then(|v| v.get::<f64>())
+            .map(|allocations| *allocations as u64)
+            .ok_or("Expected memory.allocations to be a number.")?;
+
+        Ok(MemoryUsage {
+            step: string("step")?,
+            peak: string("peak")?,
+            allocations,
+            allocated: string("allocated")?,
         })
     }
 }
@@ -179,6 +536,8 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::collections::BTreeMap;
+
     use crate::day;
 
     use super::{Timing, Timings};
@@ -190,26 +549,50 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 3e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 7e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse: None,
+                    solve: None,
                     total_nanos: 4e+10,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{PhaseTiming, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -220,9 +603,35 @@ mod tests {
             assert_eq!(timing.day, day!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_phases() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "parse": "2ms", "total_nanos": 1000000, "phases": [{ "step": "part_1", "name": "parse", "time": "0.5ms" }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".to_string()));
+            assert_eq!(
+                timing.phases,
+                [PhaseTiming {
+                    step: "part_1".into(),
+                    name: "parse".into(),
+                    time: "0.5ms".into()
+                }]
+            );
+        }
+
+        #[test]
+        fn handles_json_samples_and_answers() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "samples": { "part_1": 512 }, "answers": { "part_1": "42" } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.samples.get("part_1"), Some(&512));
+            assert_eq!(timing.answers.get("part_1").map(String::as_str), Some("42"));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -269,6 +678,8 @@ mod tests {
     }
 
     mod is_day_complete {
+        use std::collections::BTreeMap;
+
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -281,11 +692,39 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse: None,
+                    solve: None,
                     total_nanos: 3_000_000_000_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
+        }
+
+        #[test]
+        fn handles_days_solved_together() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: None,
+                    part_2: None,
+                    parse: None,
+                    solve: Some("3ms".into()),
+                    total_nanos: 3_000_000_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -295,11 +734,18 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse: None,
+                    solve: None,
                     total_nanos: 1_000_000_000_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -309,15 +755,24 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    solve: None,
                     total_nanos: 0.0,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
+        use std::collections::BTreeMap;
+
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -333,7 +788,14 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    solve: None,
                     total_nanos: 0_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 }],
             };
             let merged = timings.merge(&other);
@@ -353,7 +815,14 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
+                    solve: None,
                     total_nanos: 0_f64,
+                    phases: vec![],
+                    memory: vec![],
+                    samples: BTreeMap::new(),
+                    spreads: BTreeMap::new(),
+                    answers: BTreeMap::new(),
                 }],
             };
             let merged = timings.merge(&other);
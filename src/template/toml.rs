@@ -0,0 +1,141 @@
+/// A small subset of TOML: `[section]` and `[[array]]` headers, `key = value` pairs with string,
+/// integer, boolean and array values, and `#` comments.
+///
+/// Shared by `aoc.toml`, the example manifests and `build.rs`, which includes this file with `#[path]`,
+/// so that it must not depend on the rest of the crate.
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+/// Parses the supported TOML subset into values keyed by `section.key`.
+/// Tables of an `[[array]]` are keyed by their index, e.g. `array.0.key`.
+pub(crate) fn parse_toml(s: &str) -> Result<HashMap<String, Value>, String> {
+    let mut values = HashMap::new();
+    let mut section = String::new();
+    let mut array_lengths: HashMap<String, usize> = HashMap::new();
+
+    for (index, line) in s.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("[[") {
+            let name = name
+                .strip_suffix("]]")
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or(format!("line {line_number}: invalid array header."))?;
+            let length = array_lengths.entry(name.to_string()).or_default();
+            section = format!("{name}.{length}");
+            *length += 1;
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            section = name
+                .strip_suffix(']')
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .ok_or(format!("line {line_number}: invalid section header."))?;
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {line_number}: expected `key = value`."))?;
+
+        let key = if section.is_empty() {
+            key.trim().to_string()
+        } else {
+            format!("{section}.{}", key.trim())
+        };
+
+        let value =
+            parse_value(value.trim()).ok_or(format!("line {line_number}: invalid value."))?;
+
+        if values.insert(key.clone(), value).is_some() {
+            return Err(format!("line {line_number}: duplicate key `{key}`."));
+        }
+    }
+
+    Ok(values)
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    if let Some(inner) = s.strip_prefix('[') {
+        let inner = inner.strip_suffix(']')?.trim();
+        if inner.is_empty() {
+            return Some(Value::Array(vec![]));
+        }
+        return inner
+            .split(',')
+            .map(str::trim)
+            // allow a trailing comma.
+            .filter(|item| !item.is_empty())
+            .map(parse_value)
+            .collect::<Option<_>>()
+            .map(Value::Array);
+    }
+
+    if let Some(inner) = s.strip_prefix('"') {
+        let inner = inner.strip_suffix('"')?;
+        return (!inner.contains('"')).then(|| Value::String(inner.into()));
+    }
+
+    match s {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        _ => s.replace('_', "").parse().ok().map(Value::Integer),
+    }
+}
+
+/// Removes a `#` comment, unless it is part of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_toml, Value};
+
+    #[test]
+    fn parses_arrays_of_tables() {
+        let values = parse_toml("[[example]]\nfile = \"a\"\n\n[[example]]\nfile = \"b\"").unwrap();
+        assert_eq!(
+            values.get("example.0.file"),
+            Some(&Value::String("a".into()))
+        );
+        assert_eq!(
+            values.get("example.1.file"),
+            Some(&Value::String("b".into()))
+        );
+    }
+
+    #[test]
+    fn keeps_comment_characters_in_strings() {
+        let values = parse_toml("[paths]\ndata = \"my#dir\" # comment").unwrap();
+        assert_eq!(
+            values.get("paths.data"),
+            Some(&Value::String("my#dir".into()))
+        );
+    }
+}
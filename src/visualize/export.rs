@@ -0,0 +1,230 @@
+/// Exports frames as PPM / PNG images and animated GIFs.
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::{gif, Color, Frame};
+
+/// Renders a frame to RGB pixels, drawing each cell as a `scale` x `scale` square.
+/// Returns `(width, height, pixels)`.
+pub(super) fn rasterize(frame: &Frame, scale: usize) -> (usize, usize, Vec<Color>) {
+    let scale = scale.max(1);
+    let width = frame.width() * scale;
+    let height = frame.height() * scale;
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for row in frame.rows() {
+        let line: Vec<Color> = row
+            .iter()
+            .flat_map(|cell| std::iter::repeat_n(cell.pixel(), scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+
+    (width, height, pixels)
+}
+
+/// Writes a frame as a binary PPM (`P6`) image.
+pub fn write_ppm(frame: &Frame, path: impl AsRef<Path>, scale: usize) -> io::Result<()> {
+    let (width, height, pixels) = rasterize(frame, scale);
+    let mut file = BufWriter::new(File::create(path)?);
+    write!(file, "P6\n{width} {height}\n255\n")?;
+    for Color { r, g, b } in pixels {
+        file.write_all(&[r, g, b])?;
+    }
+    file.flush()
+}
+
+/// Writes a frame as a PNG image.
+pub fn write_png(frame: &Frame, path: impl AsRef<Path>, scale: usize) -> io::Result<()> {
+    let (width, height, pixels) = rasterize(frame, scale);
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&encode_png(width, height, &pixels)?)?;
+    file.flush()
+}
+
+/// Writes every frame to `dir` as `frame_0000.ppm`, `frame_0001.ppm`, ...
+pub fn export_ppm_sequence(
+    frames: &[Frame],
+    dir: impl AsRef<Path>,
+    scale: usize,
+) -> io::Result<()> {
+    export_sequence(frames, dir.as_ref(), "ppm", |frame, path| {
+        write_ppm(frame, path, scale)
+    })
+}
+
+/// Writes every frame to `dir` as `frame_0000.png`, `frame_0001.png`, ...
+pub fn export_png_sequence(
+    frames: &[Frame],
+    dir: impl AsRef<Path>,
+    scale: usize,
+) -> io::Result<()> {
+    export_sequence(frames, dir.as_ref(), "png", |frame, path| {
+        write_png(frame, path, scale)
+    })
+}
+
+/// Writes the frames as a looping animated GIF. Frames with more than 256 distinct colours
+/// are reduced to a fixed 216 colour palette.
+pub fn write_gif(
+    frames: &[Frame],
+    path: impl AsRef<Path>,
+    scale: usize,
+    fps: f64,
+) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    gif::encode(&mut file, frames, scale, fps)?;
+    file.flush()
+}
+
+fn export_sequence(
+    frames: &[Frame],
+    dir: &Path,
+    extension: &str,
+    write: impl Fn(&Frame, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    frames.iter().enumerate().try_for_each(|(index, frame)| {
+        write(frame, &dir.join(format!("frame_{index:04}.{extension}")))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const MAX_STORED_BLOCK_SIZE: usize = 65_535;
+
+/// Encodes an RGB image as PNG. The image data is stored uncompressed, which keeps the
+/// encoder tiny at the cost of larger files.
+fn encode_png(width: usize, height: usize, pixels: &[Color]) -> io::Result<Vec<u8>> {
+    let to_u32 = |value: usize| {
+        u32::try_from(value)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "image too large"))
+    };
+
+    let mut header = vec![];
+    header.extend_from_slice(&to_u32(width)?.to_be_bytes());
+    header.extend_from_slice(&to_u32(height)?.to_be_bytes());
+    // 8 bit depth, truecolor, deflate, default filters, no interlacing.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+    for row in pixels.chunks(width.max(1)).take(height) {
+        // filter type "none" for each scanline.
+        raw.push(0);
+        for Color { r, g, b } in row {
+            raw.extend_from_slice(&[*r, *g, *b]);
+        }
+    }
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    write_png_chunk(&mut png, b"IHDR", &header);
+    write_png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_png_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    // NOTE: chunk data is limited by the size of the stored zlib stream which fits into memory.
+    #[allow(clippy::cast_possible_truncation)]
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK_SIZE).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_last = u8::from(blocks.peek().is_none());
+        // NOTE: blocks are at most 65535 bytes long.
+        #[allow(clippy::cast_possible_truncation)]
+        let len = block.len() as u16;
+        out.push(is_last);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65_521;
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, encode_png, rasterize, zlib_stored};
+    use crate::visualize::{Cell, Color, Frame};
+
+    #[test]
+    fn rasterizes_with_scale() {
+        let mut frame = Frame::new(2, 1);
+        frame.set(1, 0, Cell::colored('#', Color::RED));
+        let (width, height, pixels) = rasterize(&frame, 2);
+        assert_eq!((width, height), (4, 2));
+        assert_eq!(
+            pixels,
+            [Color::BLACK, Color::BLACK, Color::RED, Color::RED].repeat(2)
+        );
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn wraps_stored_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+        // header + 2 block headers + data + checksum
+        assert_eq!(stream.len(), 2 + 2 * 5 + 70_000 + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + 65_535], 1);
+    }
+
+    #[test]
+    fn encodes_png_header() {
+        let png = encode_png(3, 2, &[Color::WHITE; 6]).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+}
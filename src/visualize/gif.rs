@@ -0,0 +1,367 @@
+/// Minimal animated GIF (GIF89a) encoder.
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::export::rasterize;
+use super::{Color, Frame};
+
+const MAX_CODE: u16 = 4096;
+const MAX_PALETTE_SIZE: usize = 256;
+
+pub(super) fn encode(
+    out: &mut impl Write,
+    frames: &[Frame],
+    scale: usize,
+    fps: f64,
+) -> io::Result<()> {
+    let images: Vec<(usize, usize, Vec<Color>)> =
+        frames.iter().map(|frame| rasterize(frame, scale)).collect();
+
+    let screen_width = to_u16(images.iter().map(|image| image.0).max().unwrap_or(0))?;
+    let screen_height = to_u16(images.iter().map(|image| image.1).max().unwrap_or(0))?;
+
+    let palette = Palette::new(images.iter().flat_map(|image| image.2.iter().copied()));
+    let delay = if fps > 0.0 {
+        // NOTE: the value is clamped to the valid range before the cast.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let centiseconds = (100.0 / fps).round().clamp(1.0, f64::from(u16::MAX)) as u16;
+        centiseconds
+    } else {
+        10
+    };
+
+    out.write_all(b"GIF89a")?;
+    out.write_all(&screen_width.to_le_bytes())?;
+    out.write_all(&screen_height.to_le_bytes())?;
+    // global colour table present, 8 bit colour resolution, size of the colour table.
+    out.write_all(&[0xf0 | (palette.bits - 1), 0, 0])?;
+    for Color { r, g, b } in palette.table() {
+        out.write_all(&[r, g, b])?;
+    }
+
+    // loop forever.
+    out.write_all(&[0x21, 0xff, 0x0b])?;
+    out.write_all(b"NETSCAPE2.0")?;
+    out.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+    for (width, height, pixels) in &images {
+        out.write_all(&[0x21, 0xf9, 0x04, 0x00])?;
+        out.write_all(&delay.to_le_bytes())?;
+        out.write_all(&[0x00, 0x00])?;
+
+        out.write_all(&[0x2c, 0, 0, 0, 0])?;
+        out.write_all(&to_u16(*width)?.to_le_bytes())?;
+        out.write_all(&to_u16(*height)?.to_le_bytes())?;
+        out.write_all(&[0x00])?;
+
+        let min_code_size = palette.bits.max(2);
+        let indices: Vec<u8> = pixels
+            .iter()
+            .map(|color| palette.index_of(*color))
+            .collect();
+        out.write_all(&[min_code_size])?;
+        for block in lzw_compress(&indices, min_code_size).chunks(255) {
+            // NOTE: sub-blocks are at most 255 bytes long.
+            #[allow(clippy::cast_possible_truncation)]
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0x00])?;
+    }
+
+    out.write_all(&[0x3b])
+}
+
+fn to_u16(value: usize) -> io::Result<u16> {
+    u16::try_from(value)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "image too large for GIF"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The global colour table: either the exact colours of the animation or a 6x6x6 colour cube.
+struct Palette {
+    colors: Vec<Color>,
+    lookup: Option<HashMap<Color, u8>>,
+    bits: u8,
+}
+
+impl Palette {
+    fn new(pixels: impl Iterator<Item = Color>) -> Self {
+        let mut colors: Vec<Color> = vec![];
+        let mut lookup: HashMap<Color, u8> = HashMap::new();
+
+        for color in pixels {
+            if lookup.contains_key(&color) {
+                continue;
+            }
+            if colors.len() == MAX_PALETTE_SIZE {
+                return Self::color_cube();
+            }
+            // NOTE: there are at most 256 colours at this point.
+            #[allow(clippy::cast_possible_truncation)]
+            lookup.insert(color, colors.len() as u8);
+            colors.push(color);
+        }
+
+        let bits = Self::bits_for(colors.len());
+        Self {
+            colors,
+            lookup: Some(lookup),
+            bits,
+        }
+    }
+
+    fn color_cube() -> Self {
+        let levels = (0..6_u8).map(|level| level * 51);
+        let colors = levels
+            .clone()
+            .flat_map(|r| levels.clone().map(move |g| (r, g)))
+            .flat_map(|(r, g)| levels.clone().map(move |b| Color::rgb(r, g, b)))
+            .collect();
+        Self {
+            colors,
+            lookup: None,
+            bits: 8,
+        }
+    }
+
+    fn bits_for(len: usize) -> u8 {
+        let mut bits = 1;
+        while (1 << bits) < len {
+            bits += 1;
+        }
+        bits
+    }
+
+    fn index_of(&self, color: Color) -> u8 {
+        match &self.lookup {
+            Some(lookup) => lookup[&color],
+            None => {
+                let level = |channel: u8| (u16::from(channel) * 6 / 256) as u8;
+                level(color.r) * 36 + level(color.g) * 6 + level(color.b)
+            }
+        }
+    }
+
+    /// The colour table padded to a power of two.
+    fn table(&self) -> impl Iterator<Item = Color> + '_ {
+        self.colors
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(Color::BLACK))
+            .take(1 << self.bits)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Variable-length LZW as used by GIF, including clear codes when the code table is full.
+struct LzwEncoder {
+    min_code_size: u8,
+    code_size: u8,
+    next_code: u16,
+    reset_code_size: bool,
+    buffer: u32,
+    buffered_bits: u8,
+    out: Vec<u8>,
+}
+
+impl LzwEncoder {
+    fn clear_code(&self) -> u16 {
+        1 << self.min_code_size
+    }
+
+    fn max_code(&self) -> u16 {
+        if self.code_size == 12 {
+            MAX_CODE
+        } else {
+            (1 << self.code_size) - 1
+        }
+    }
+
+    fn emit(&mut self, code: u16) {
+        self.buffer |= u32::from(code) << self.buffered_bits;
+        self.buffered_bits += self.code_size;
+        while self.buffered_bits >= 8 {
+            self.out.push((self.buffer & 0xff) as u8);
+            self.buffer >>= 8;
+            self.buffered_bits -= 8;
+        }
+
+        // the decoder grows its code size one code later than the table, mirror that here.
+        if self.reset_code_size {
+            self.code_size = self.min_code_size + 1;
+            self.reset_code_size = false;
+        } else if self.next_code > self.max_code() {
+            self.code_size += 1;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.buffered_bits > 0 {
+            self.out.push((self.buffer & 0xff) as u8);
+        }
+        self.out
+    }
+}
+
+fn lzw_compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let mut encoder = LzwEncoder {
+        min_code_size,
+        code_size: min_code_size + 1,
+        next_code: (1 << min_code_size) + 2,
+        reset_code_size: false,
+        buffer: 0,
+        buffered_bits: 0,
+        out: vec![],
+    };
+    let clear_code = encoder.clear_code();
+    let end_code = clear_code + 1;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+
+    encoder.emit(clear_code);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        encoder.emit(end_code);
+        return encoder.finish();
+    };
+
+    let mut prefix = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        encoder.emit(prefix);
+
+        if encoder.next_code < MAX_CODE {
+            table.insert((prefix, index), encoder.next_code);
+            encoder.next_code += 1;
+        } else {
+            table.clear();
+            encoder.next_code = clear_code + 2;
+            encoder.reset_code_size = true;
+            encoder.emit(clear_code);
+        }
+
+        prefix = u16::from(index);
+    }
+
+    encoder.emit(prefix);
+    encoder.emit(end_code);
+    encoder.finish()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{encode, lzw_compress, Palette};
+    use crate::visualize::{Cell, Color, Frame};
+
+    /// Reference decoder used to verify the encoder round-trips.
+    fn lzw_decompress(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear_code = 1_u16 << min_code_size;
+        let end_code = clear_code + 1;
+        let mut code_size = min_code_size + 1;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear_code).map(|i| vec![i as u8]).collect();
+            table.push(vec![]);
+            table.push(vec![]);
+        };
+        reset(&mut table);
+
+        let (mut buffer, mut bits, mut pos) = (0_u32, 0_u8, 0);
+        let mut previous: Option<Vec<u8>> = None;
+        let mut out = vec![];
+
+        loop {
+            while bits < code_size {
+                buffer |= u32::from(data[pos]) << bits;
+                pos += 1;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as u16;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear_code {
+                reset(&mut table);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end_code {
+                return out;
+            }
+
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("invalid code"),
+            };
+            if let Some(previous) = previous {
+                table.push([previous, vec![entry[0]]].concat());
+            }
+            if table.len() == (1 << code_size) && code_size < 12 {
+                code_size += 1;
+            }
+            out.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn round_trips_lzw() {
+        let mut state = 12345_u32;
+        let noisy: Vec<u8> = (0..20_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ((state >> 16) % 4) as u8
+            })
+            .collect();
+        let repetitive: Vec<u8> = (0..5000).map(|i| (i % 7) as u8).collect();
+
+        for (data, min_code_size) in [(noisy, 2), (repetitive, 3), (vec![], 2), (vec![1], 2)] {
+            let compressed = lzw_compress(&data, min_code_size);
+            assert_eq!(lzw_decompress(&compressed, min_code_size), data);
+        }
+    }
+
+    #[test]
+    fn builds_palettes() {
+        let palette = Palette::new([Color::RED, Color::BLUE, Color::RED, Color::GREEN].into_iter());
+        assert_eq!(palette.bits, 2);
+        assert_eq!(palette.index_of(Color::GREEN), 2);
+        assert_eq!(palette.table().count(), 4);
+
+        let many_colors = (0..=255_u8).flat_map(|r| [Color::rgb(r, 0, 0), Color::rgb(r, 1, 0)]);
+        let palette = Palette::new(many_colors);
+        assert_eq!(palette.bits, 8);
+        assert_eq!(palette.index_of(Color::WHITE), 215);
+    }
+
+    #[test]
+    fn encodes_animations() {
+        let frames: Vec<Frame> = (0..3)
+            .map(|step| {
+                Frame::from_fn(3, 3, |x, y| {
+                    if x == step && y == step {
+                        Cell::colored('#', Color::RED)
+                    } else {
+                        Cell::EMPTY
+                    }
+                })
+            })
+            .collect();
+        let mut out = vec![];
+        encode(&mut out, &frames, 2, 10.0).unwrap();
+        assert_eq!(&out[..6], b"GIF89a");
+        assert_eq!(&out[6..10], &[6, 0, 6, 0]);
+        assert!(out.iter().filter(|&&b| b == 0x2c).count() >= 3);
+        assert_eq!(out.last(), Some(&0x3b));
+    }
+}
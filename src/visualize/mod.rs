@@ -0,0 +1,293 @@
+/// Records frames of grid simulations and replays or exports them.
+///
+/// This module is only available with the `visualize` feature, e.g.
+/// `cargo run --release --features visualize --bin 14`, so that benchmark builds don't pay for it.
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+mod export;
+mod gif;
+mod terminal;
+
+pub use export::{export_png_sequence, export_ppm_sequence, write_gif, write_png, write_ppm};
+pub use terminal::Player;
+
+/// A 24-bit RGB colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(40, 180, 60);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(240, 200, 30);
+
+    #[must_use]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// A single grid cell: the glyph shown in the terminal and an optional colour.
+///
+/// Cells without a colour render in the terminal's default colour. In images, they become
+/// [`Color::WHITE`] unless the glyph is whitespace, in which case they are part of the background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub const EMPTY: Cell = Cell::new(' ');
+
+    #[must_use]
+    pub const fn new(glyph: char) -> Self {
+        Self { glyph, color: None }
+    }
+
+    #[must_use]
+    pub const fn colored(glyph: char, color: Color) -> Self {
+        Self {
+            glyph,
+            color: Some(color),
+        }
+    }
+
+    /// The colour used for this cell when rendering to an image.
+    #[must_use]
+    pub fn pixel(&self) -> Color {
+        match self.color {
+            Some(color) => color,
+            None if self.glyph.is_whitespace() => Color::BLACK,
+            None => Color::WHITE,
+        }
+    }
+}
+
+impl From<char> for Cell {
+    fn from(glyph: char) -> Self {
+        Cell::new(glyph)
+    }
+}
+
+/// A snapshot of a grid simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Creates an empty frame of the given size.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::EMPTY; width * height],
+            caption: None,
+        }
+    }
+
+    /// Creates a frame by calling `cell_at(x, y)` for each position.
+    pub fn from_fn<C: Into<Cell>>(
+        width: usize,
+        height: usize,
+        mut cell_at: impl FnMut(usize, usize) -> C,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell_at(x, y).into())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+            caption: None,
+        }
+    }
+
+    /// Creates a frame from the lines of a string, e.g. the output of a `Display` impl.
+    /// Shorter lines are padded with empty cells.
+    #[must_use]
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        Self::from_fn(width, lines.len(), |x, y| {
+            lines[y].get(x).copied().map_or(Cell::EMPTY, Cell::new)
+        })
+    }
+
+    /// Attaches a line of text that is shown below the grid during playback.
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// Sets the cell at `(x, y)`. Positions outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, cell: impl Into<Cell>) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell.into();
+        }
+    }
+
+    /// Iterates the rows of the frame from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+}
+
+impl Display for Frame {
+    /// Renders the glyphs without colours.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            let line: String = row.iter().map(|cell| cell.glyph).collect();
+            writeln!(f, "{line}")?;
+        }
+        if let Some(caption) = &self.caption {
+            writeln!(f, "{caption}")?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Collects the frames of a simulation.
+///
+/// ```
+/// # use advent_of_code::visualize::{Frame, Recorder};
+/// let mut recorder = Recorder::with_limit(100);
+/// for step in 0..3 {
+///     recorder.record(Frame::from_fn(3, 1, |x, _| if x == step { '#' } else { '.' }));
+/// }
+/// assert_eq!(recorder.frames().len(), 3);
+/// ```
+///
+/// The recorded frames can be passed to the player and the exporters:
+///
+/// ```no_run
+/// # use advent_of_code::visualize::{write_gif, Player, Recorder};
+/// # let mut recorder = Recorder::new();
+/// Player::new().fps(20.0).play(recorder.frames()).unwrap();
+/// write_gif(recorder.frames(), "day14.gif", 4, 20.0).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    frames: VecDeque<Frame>,
+    limit: Option<usize>,
+}
+
+impl Recorder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a recorder that keeps only the latest `limit` frames, e.g. for long simulations.
+    #[must_use]
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            limit: Some(limit),
+        }
+    }
+
+    pub fn record(&mut self, frame: Frame) {
+        if let Some(limit) = self.limit {
+            if limit == 0 {
+                return;
+            }
+            if self.frames.len() == limit {
+                self.frames.pop_front();
+            }
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// The recorded frames, oldest first.
+    #[must_use]
+    pub fn frames(&mut self) -> &[Frame] {
+        self.frames.make_contiguous()
+    }
+
+    #[must_use]
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames.into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cell, Color, Frame, Recorder};
+
+    #[test]
+    fn builds_frames() {
+        let mut frame = Frame::from_fn(3, 2, |x, y| if x == y { '#' } else { '.' });
+        frame.set(2, 1, Cell::colored('@', Color::RED));
+        frame.set(5, 5, '!');
+        assert_eq!(frame.get(0, 0), Some(Cell::new('#')));
+        assert_eq!(frame.get(2, 1), Some(Cell::colored('@', Color::RED)));
+        assert_eq!(frame.get(3, 0), None);
+        assert_eq!(frame.to_string(), "#..\n.#@\n");
+    }
+
+    #[test]
+    fn parses_frames_from_text() {
+        let frame = Frame::from_text("##\n#\n").with_caption("step 1");
+        assert_eq!(frame.width(), 2);
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.get(1, 1), Some(Cell::EMPTY));
+        assert_eq!(frame.to_string(), "##\n# \nstep 1\n");
+    }
+
+    #[test]
+    fn maps_cells_to_pixels() {
+        assert_eq!(Cell::EMPTY.pixel(), Color::BLACK);
+        assert_eq!(Cell::new('#').pixel(), Color::WHITE);
+        assert_eq!(Cell::colored(' ', Color::BLUE).pixel(), Color::BLUE);
+    }
+
+    #[test]
+    fn limits_recorded_frames() {
+        let mut recorder = Recorder::with_limit(2);
+        for caption in ["a", "b", "c"] {
+            recorder.record(Frame::new(1, 1).with_caption(caption));
+        }
+        let captions: Vec<_> = recorder.frames().iter().map(|f| f.caption()).collect();
+        assert_eq!(captions, [Some("b"), Some("c")]);
+    }
+}
@@ -0,0 +1,156 @@
+/// Plays back recorded frames in the terminal using ANSI escape codes.
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{Color, Frame};
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J";
+const ANSI_CURSOR_HOME: &str = "\x1b[H";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// Terminal playback settings.
+///
+/// ```no_run
+/// # use advent_of_code::visualize::{Frame, Player};
+/// # let frames: Vec<Frame> = vec![];
+/// Player::new().fps(10.0).cell_width(2).play(&frames).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Player {
+    fps: f64,
+    repeat: usize,
+    cell_width: usize,
+    color: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            fps: 15.0,
+            repeat: 1,
+            cell_width: 1,
+            color: true,
+        }
+    }
+}
+
+impl Player {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Frames shown per second. Values that are not positive are ignored.
+    #[must_use]
+    pub fn fps(mut self, fps: f64) -> Self {
+        if fps > 0.0 {
+            self.fps = fps;
+        }
+        self
+    }
+
+    /// Number of times the frames are played back.
+    #[must_use]
+    pub fn repeat(mut self, repeat: usize) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Number of times each glyph is repeated horizontally. Terminal cells are about twice as high
+    /// as they are wide, so `2` makes grids look square.
+    #[must_use]
+    pub fn cell_width(mut self, cell_width: usize) -> Self {
+        self.cell_width = cell_width.max(1);
+        self
+    }
+
+    /// Enables or disables ANSI colours, e.g. when the output is piped into a file.
+    #[must_use]
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Renders a single frame to a string, including colour escape codes if enabled.
+    #[must_use]
+    pub fn render(&self, frame: &Frame) -> String {
+        let mut out = String::new();
+        for row in frame.rows() {
+            let mut current_color: Option<Color> = None;
+            for cell in row {
+                if self.color && cell.color != current_color {
+                    match cell.color {
+                        Some(Color { r, g, b }) => out.push_str(&format!("\x1b[38;2;{r};{g};{b}m")),
+                        None => out.push_str(ANSI_RESET),
+                    }
+                    current_color = cell.color;
+                }
+                (0..self.cell_width).for_each(|_| out.push(cell.glyph));
+            }
+            if current_color.is_some() {
+                out.push_str(ANSI_RESET);
+            }
+            out.push('\n');
+        }
+        if let Some(caption) = frame.caption() {
+            if self.color {
+                out.push_str(&format!("{ANSI_ITALIC}{caption}{ANSI_RESET}\n"));
+            } else {
+                out.push_str(caption);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Plays the frames on stdout, redrawing in place.
+    pub fn play(&self, frames: &[Frame]) -> io::Result<()> {
+        let frame_duration = Duration::from_secs_f64(1.0 / self.fps);
+        let mut stdout = io::stdout().lock();
+
+        write!(stdout, "{ANSI_HIDE_CURSOR}{ANSI_CLEAR_SCREEN}")?;
+        let result = (|| {
+            for _ in 0..self.repeat {
+                for frame in frames {
+                    let timer = Instant::now();
+                    write!(stdout, "{ANSI_CURSOR_HOME}{}", self.render(frame))?;
+                    stdout.flush()?;
+                    if let Some(remaining) = frame_duration.checked_sub(timer.elapsed()) {
+                        thread::sleep(remaining);
+                    }
+                }
+            }
+            Ok(())
+        })();
+        write!(stdout, "{ANSI_SHOW_CURSOR}")?;
+        stdout.flush()?;
+        result
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Player;
+    use crate::visualize::{Cell, Color, Frame};
+
+    #[test]
+    fn renders_plain_frames() {
+        let frame = Frame::from_text("#.\n.#").with_caption("t=1");
+        let rendered = Player::new().color(false).cell_width(2).render(&frame);
+        assert_eq!(rendered, "##..\n..##\nt=1\n");
+    }
+
+    #[test]
+    fn renders_colors_once_per_run() {
+        let mut frame = Frame::from_text("...");
+        frame.set(1, 0, Cell::colored('#', Color::RED));
+        frame.set(2, 0, Cell::colored('#', Color::RED));
+        let rendered = Player::new().render(&frame);
+        assert_eq!(rendered, ".\x1b[38;2;220;50;47m##\x1b[0m\n");
+    }
+}